@@ -0,0 +1,32 @@
+use aoc::parse::{self, ParseResult};
+use aoc::{Solution, Value};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input<'a> = Vec<u32>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        input.lines().map(parse::num).collect()
+    }
+
+    fn part1(nums: &Self::Input<'_>) -> impl Into<Value> {
+        count_increases(nums.iter().copied())
+    }
+
+    fn part2(nums: &Self::Input<'_>) -> impl Into<Value> {
+        count_increases(nums.windows(3).map(|s| s.iter().sum::<u32>()))
+    }
+}
+
+fn count_increases(mut nums: impl Iterator<Item = u32>) -> usize {
+    let mut prev = nums.next().unwrap();
+    let mut count = 0;
+    for num in nums {
+        if prev < num {
+            count += 1;
+        }
+        prev = num;
+    }
+    count
+}
@@ -0,0 +1,68 @@
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{Solution, Value};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input<'a> = Vec<Move>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        input.lines().map(Move::from_input).collect()
+    }
+
+    fn part1(moves: &Self::Input<'_>) -> impl Into<Value> {
+        let mut submarine = Point::default();
+        for m in moves {
+            match m.0 {
+                MoveType::Forward => submarine.x += m.1,
+                MoveType::Up => submarine.y -= m.1,
+                MoveType::Down => submarine.y += m.1,
+            }
+        }
+        submarine.x * submarine.y
+    }
+
+    fn part2(moves: &Self::Input<'_>) -> impl Into<Value> {
+        let mut submarine = Point::default();
+        for m in moves {
+            match m.0 {
+                MoveType::Forward => {
+                    submarine.x += m.1;
+                    submarine.y += submarine.aim * m.1
+                }
+                MoveType::Up => submarine.aim -= m.1,
+                MoveType::Down => submarine.aim += m.1,
+            }
+        }
+        submarine.x * submarine.y
+    }
+}
+
+// #[derive(Debug)]
+enum MoveType {
+    Up,
+    Down,
+    Forward,
+}
+
+pub struct Move(MoveType, i32);
+
+impl Move {
+    fn from_input(s: &str) -> ParseResult<Self> {
+        let (kind, n) = parse::split_once(s, " ")?;
+        let kind = match kind {
+            "forward" => MoveType::Forward,
+            "up" => MoveType::Up,
+            "down" => MoveType::Down,
+            _ => return Err(ParseError::new(kind, "forward, up or down")),
+        };
+        Ok(Self(kind, parse::num(n)?))
+    }
+}
+
+#[derive(Default)]
+struct Point {
+    x: i32,
+    y: i32,
+    aim: i32,
+}
@@ -0,0 +1,72 @@
+use std::collections::BTreeSet;
+
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{Solution, Value};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        input
+            .lines()
+            .map(|line| {
+                parse::chars(line, |c| match c {
+                    '0' | '1' => Ok(()),
+                    _ => Err("a binary digit"),
+                })?;
+                match line.len() {
+                    12 => Ok(line),
+                    _ => Err(ParseError::new(line, "12 binary digits")),
+                }
+            })
+            .collect()
+    }
+
+    fn part1(report: &Self::Input<'_>) -> impl Into<Value> {
+        let mut ones = [0usize; 12];
+        for line in report {
+            for (i, c) in line.chars().enumerate() {
+                if c == '1' {
+                    ones[i] += 1;
+                }
+            }
+        }
+        let total = report.len();
+        let mut gamma = 0u32;
+        for count in ones {
+            gamma <<= 1;
+            gamma |= (count > total / 2) as u32;
+        }
+        let epsilon = gamma ^ 0xFFF;
+        gamma * epsilon
+    }
+
+    // ported from the original C++ solution (03_2.cc)
+    fn part2(report: &Self::Input<'_>) -> impl Into<Value> {
+        let pool: BTreeSet<&str> = report.iter().copied().collect();
+        let o2 = calc(pool.clone(), |a, b| a >= b);
+        let co2 = calc(pool, |a, b| a < b);
+        o2 * co2
+    }
+}
+
+fn calc(mut pool: BTreeSet<&str>, cmp: fn(usize, usize) -> bool) -> u32 {
+    for i in 0..12 {
+        if pool.len() <= 1 {
+            break;
+        }
+        let count_one = pool.iter().filter(|s| s.as_bytes()[i] == b'1').count();
+        let least_common = if cmp(count_one, pool.len() - count_one) {
+            b'0'
+        } else {
+            b'1'
+        };
+        pool.retain(|s| s.as_bytes()[i] != least_common);
+    }
+    match pool.first() {
+        Some(s) if pool.len() == 1 => u32::from_str_radix(s, 2).unwrap(),
+        _ => 0,
+    }
+}
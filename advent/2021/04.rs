@@ -0,0 +1,107 @@
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{Solution, Value};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input<'a> = (Vec<u32>, Vec<Board>);
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        let mut sections = parse::sections(input.lines());
+        let sequence = sections
+            .next()
+            .ok_or_else(|| ParseError::after(input, "the drawn numbers"))?[0]
+            .split(',')
+            .map(parse::num)
+            .collect::<ParseResult<Vec<u32>>>()?;
+
+        let boards = sections
+            .map(|lines| {
+                if lines.len() != 5 {
+                    return Err(ParseError::after(lines[lines.len() - 1], "5 board rows"));
+                }
+                let rows = lines
+                    .iter()
+                    .map(|line| {
+                        line.split_whitespace()
+                            .map(|x| Ok((parse::num(x)?, false)))
+                            .collect()
+                    })
+                    .collect::<ParseResult<_>>()?;
+                Ok(Board(rows))
+            })
+            .collect::<ParseResult<_>>()?;
+        Ok((sequence, boards))
+    }
+
+    fn part1((sequence, boards): &Self::Input<'_>) -> impl Into<Value> {
+        let mut boards = boards.clone();
+        for &num in sequence {
+            for board in boards.iter_mut() {
+                board.mark(num);
+            }
+            if let Some(board) = boards.iter().find(|b| b.is_bingo()) {
+                return num * board.get_score();
+            }
+        }
+        unreachable!();
+    }
+
+    fn part2((sequence, boards): &Self::Input<'_>) -> impl Into<Value> {
+        let mut boards = boards.clone();
+        let mut last_pts = 0u32;
+        for &num in sequence {
+            for board in boards.iter_mut() {
+                board.mark(num);
+            }
+            for i in (0..boards.len()).rev() {
+                if boards[i].is_bingo() {
+                    last_pts = num * boards[i].get_score();
+                    boards.remove(i);
+                }
+            }
+        }
+        last_pts
+    }
+}
+
+#[derive(Clone)]
+pub struct Board(Vec<Vec<(u32, bool)>>);
+
+impl Board {
+    fn mark(&mut self, n: u32) {
+        for row in self.0.iter_mut() {
+            for (num, called) in row.iter_mut() {
+                if *num == n {
+                    *called = true;
+                }
+            }
+        }
+    }
+
+    fn is_bingo(&self) -> bool {
+        for row in self.0.iter() {
+            if row.iter().all(|(_, called)| *called) {
+                return true;
+            }
+        }
+        for i in 0..self.0[0].len() {
+            if self.0.iter().all(|row| row[i].1) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn get_score(&self) -> u32 {
+        self.0
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .filter(|(_, called)| !*called)
+                    .map(|(number, _)| *number)
+                    .sum::<u32>()
+            })
+            .sum()
+    }
+}
@@ -0,0 +1,50 @@
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{Solution, Value};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input<'a> = Vec<usize>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        let line = input.lines().next().unwrap_or("");
+        line.split(',')
+            .map(|x| match parse::num(x)? {
+                n @ 0..=8 => Ok(n),
+                _ => Err(ParseError::new(x, "a timer between 0 and 8")),
+            })
+            .collect()
+    }
+
+    // this is solved by simulation
+    // this can also be solved using part 2's approach (just replace the iteration limit to 80)
+    fn part1(fish: &Self::Input<'_>) -> impl Into<Value> {
+        let mut a = fish.clone();
+        for _ in 0..80 {
+            let ln = a.len();
+            for i in 0..ln {
+                if a[i] == 0 {
+                    a[i] = 6;
+                    a.push(8);
+                } else {
+                    a[i] -= 1;
+                }
+            }
+        }
+        a.len()
+    }
+
+    fn part2(fish: &Self::Input<'_>) -> impl Into<Value> {
+        let mut count = [0u64; 9]; // number of fish with timer i
+        for &f in fish {
+            count[f] += 1;
+        }
+        for _ in 0..256 {
+            // newborn count = number of fish with timer 0 at last iteration
+            count.rotate_left(1);
+            // also those fish will get a timer of 6 after producing
+            count[6] += count[8];
+        }
+        count.iter().sum::<u64>()
+    }
+}
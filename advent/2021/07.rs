@@ -0,0 +1,34 @@
+use aoc::parse::{self, ParseResult};
+use aoc::{Solution, Value};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input<'a> = Vec<i32>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        let line = input.lines().next().unwrap_or("");
+        line.split(',').map(parse::num).collect()
+    }
+
+    fn part1(crabs: &Self::Input<'_>) -> impl Into<Value> {
+        let mut a = crabs.clone();
+        a.sort();
+        let median = a[a.len() / 2];
+        a.iter().map(|x| (x - median).abs()).sum::<i32>()
+    }
+
+    fn part2(crabs: &Self::Input<'_>) -> impl Into<Value> {
+        let min_crab = *crabs.iter().min().unwrap();
+        let max_crab = *crabs.iter().max().unwrap();
+        (min_crab..=max_crab)
+            .map(|pos| {
+                crabs
+                    .iter()
+                    .map(|crab| (1i32..=((crab - pos).abs())).sum::<i32>())
+                    .sum::<i32>()
+            })
+            .min()
+            .unwrap()
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{Solution, Value};
+
+pub struct Day08;
+
+// the ten unique signal patterns and the four output digits of a display
+type Entry<'a> = (Vec<&'a str>, Vec<&'a str>);
+
+impl Solution for Day08 {
+    type Input<'a> = Vec<Entry<'a>>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        input
+            .lines()
+            .map(|line| {
+                let (pat, out) = parse::split_once(line, " | ")?;
+                let pat = signals(pat)?;
+                if pat.len() != 10 {
+                    return Err(ParseError::new(line, "10 signal patterns"));
+                }
+                Ok((pat, signals(out)?))
+            })
+            .collect()
+    }
+
+    fn part1(entries: &Self::Input<'_>) -> impl Into<Value> {
+        entries
+            .iter()
+            .map(|(_, out)| {
+                out.iter()
+                    .filter(|s| matches!(s.len(), 2 | 4 | 3 | 7))
+                    .count()
+            })
+            .sum::<usize>()
+    }
+
+    fn part2(entries: &Self::Input<'_>) -> impl Into<Value> {
+        entries
+            .iter()
+            .map(|(pat, out)| decode(pat, out))
+            .sum::<u32>()
+    }
+}
+
+fn signals(s: &str) -> ParseResult<Vec<&str>> {
+    for x in s.split(' ') {
+        parse::chars(x, |c| match c {
+            'a'..='g' => Ok(()),
+            _ => Err("a segment between 'a' and 'g'"),
+        })?;
+    }
+    Ok(s.split(' ').collect())
+}
+
+fn decode(pat: &[&str], out: &[&str]) -> u32 {
+    let mut pat = pat
+        .iter()
+        .map(|s| s.chars().collect::<HashSet<_>>())
+        .collect::<Vec<_>>();
+
+    let mut seg = HashMap::<u8, _>::new();
+    seg.insert(
+        1,
+        pat.swap_remove(pat.iter().position(|h| h.len() == 2).unwrap()),
+    );
+    seg.insert(
+        4,
+        pat.swap_remove(pat.iter().position(|h| h.len() == 4).unwrap()),
+    );
+    seg.insert(
+        7,
+        pat.swap_remove(pat.iter().position(|h| h.len() == 3).unwrap()),
+    );
+    seg.insert(
+        8,
+        pat.swap_remove(pat.iter().position(|h| h.len() == 7).unwrap()),
+    );
+
+    let v: Vec<_> = pat
+        .iter()
+        .enumerate()
+        .filter(|(_, h)| h.len() == 6)
+        .map(|(i, h)| {
+            let n = if seg[&1].intersection(h).count() == 1 {
+                6
+            } else if seg[&4].intersection(h).count() == 3 {
+                0
+            } else {
+                9
+            };
+            (i, n)
+        })
+        .collect();
+    for (i, n) in v.into_iter().rev() {
+        seg.insert(n, pat.swap_remove(i));
+    }
+
+    let v: Vec<_> = pat
+        .iter()
+        .enumerate()
+        .filter(|(_, h)| h.len() == 5)
+        .map(|(i, h)| {
+            let n = if seg[&1].intersection(h).count() == 2 {
+                3
+            } else if seg[&6].intersection(h).count() == 5 {
+                5
+            } else {
+                2
+            };
+            (i, n)
+        })
+        .collect();
+    for (i, n) in v.into_iter().rev() {
+        seg.insert(n, pat.swap_remove(i));
+    }
+
+    let mut num = 0;
+    for r in out.iter().map(|s| s.chars().collect::<HashSet<char>>()) {
+        let (k, _) = seg.iter().find(|(_, v)| &r == *v).unwrap();
+        num = num * 10 + *k as u32;
+    }
+    num
+}
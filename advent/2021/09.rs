@@ -0,0 +1,58 @@
+use aoc::grid::{Grid, Pos};
+use aoc::parse::ParseResult;
+use aoc::{Solution, Value};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input<'a> = Grid<u32>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        Grid::parse(input, |c| c.to_digit(10).ok_or("a digit"))
+    }
+
+    fn part1(board: &Self::Input<'_>) -> impl Into<Value> {
+        low_points(board)
+            .into_iter()
+            .map(|p| board[p] + 1)
+            .sum::<u32>()
+    }
+
+    fn part2(board: &Self::Input<'_>) -> impl Into<Value> {
+        let mut visited = Grid::new(board.rows(), board.cols(), false);
+
+        let mut areas: Vec<usize> = low_points(board)
+            .into_iter()
+            .map(|low| {
+                let mut pending = vec![low];
+                let mut count = 0;
+
+                while let Some(p) = pending.pop() {
+                    if visited[p] {
+                        continue;
+                    }
+                    visited[p] = true;
+                    count += 1;
+
+                    for adj in board.neighbours4(p) {
+                        if board[adj] != 9 && board[adj] >= board[p] {
+                            pending.push(adj);
+                        }
+                    }
+                }
+                count
+            })
+            .collect();
+
+        areas.sort();
+        areas.iter().rev().take(3).product::<usize>()
+    }
+}
+
+fn low_points(board: &Grid<u32>) -> Vec<Pos> {
+    board
+        .iter()
+        .filter(|&(p, &v)| board.neighbours4(p).all(|adj| v < board[adj]))
+        .map(|(p, _)| p)
+        .collect()
+}
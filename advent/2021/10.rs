@@ -0,0 +1,77 @@
+use aoc::parse::{self, ParseResult};
+use aoc::{Solution, Value};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        input
+            .lines()
+            .map(|line| {
+                parse::chars(line, |c| match c {
+                    '(' | ')' | '[' | ']' | '{' | '}' | '<' | '>' => Ok(()),
+                    _ => Err("a bracket"),
+                })?;
+                Ok(line)
+            })
+            .collect()
+    }
+
+    fn part1(lines: &Self::Input<'_>) -> impl Into<Value> {
+        lines
+            .iter()
+            .flat_map(|line| check(line).err())
+            .map(|c| {
+                match c {
+                    ')' => 3,
+                    ']' => 57,
+                    '}' => 1197,
+                    '>' => 25137,
+                    _ => 0, // i had faith in the input
+                }
+            })
+            .sum::<u64>()
+    }
+
+    fn part2(lines: &Self::Input<'_>) -> impl Into<Value> {
+        let mut scores: Vec<u64> = lines
+            .iter()
+            .flat_map(|line| check(line).ok())
+            .map(|st| {
+                let mut score = 0;
+                for c in st.into_iter().rev() {
+                    score = score * 5
+                        + match c {
+                            '(' => 1,
+                            '[' => 2,
+                            '{' => 3,
+                            '<' => 4,
+                            _ => 0,
+                        }
+                }
+                score
+            })
+            .collect();
+
+        scores.sort();
+        scores[scores.len() / 2]
+    }
+}
+
+// Returns the stack of unclosed brackets, or the first illegal character
+fn check(line: &str) -> Result<Vec<char>, char> {
+    let mut st = vec![];
+    for c in line.chars() {
+        match c {
+            '(' | '[' | '{' | '<' => st.push(c),
+            ')' if st.pop() == Some('(') => (),
+            ']' if st.pop() == Some('[') => (),
+            '}' if st.pop() == Some('{') => (),
+            '>' if st.pop() == Some('<') => (),
+            c => return Err(c),
+        };
+    }
+    Ok(st)
+}
@@ -0,0 +1,96 @@
+use aoc::grid::Grid;
+use aoc::image::Palette;
+use aoc::parse::ParseResult;
+use aoc::viz::{Scene, Screen, Visualize};
+use aoc::{Part, Solution, Value};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Grid<u32>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        Grid::parse(input, |c| c.to_digit(10).ok_or("a digit"))
+    }
+
+    fn part1(grid: &Self::Input<'_>) -> impl Into<Value> {
+        let mut grid = grid.clone();
+        (0..100).map(|_| step(&mut grid)).sum::<usize>()
+    }
+
+    fn part2(grid: &Self::Input<'_>) -> impl Into<Value> {
+        let mut grid = grid.clone();
+        let mut k = 0;
+        loop {
+            if grid.cells().iter().all(|&p| p == 0) {
+                break;
+            }
+            k += 1;
+            step(&mut grid);
+        }
+        k
+    }
+}
+
+impl Visualize for Day11 {
+    // the ones that just flashed in white, the rest brighter as they charge
+    fn palette() -> Palette {
+        let charging = Palette::gradient([10, 20, 50], [70, 140, 230], 9);
+        Palette::new(
+            [[255, 250, 220]]
+                .into_iter()
+                .chain(charging.colors().iter().copied())
+                .collect(),
+        )
+    }
+
+    // part 1 stops after 100 steps, part 2 once they all flash together
+    fn visualize(grid: &Self::Input<'_>, part: Part, screen: &mut dyn Screen) {
+        let mut grid = grid.clone();
+        let mut flashes = 0;
+        for k in 0.. {
+            let cells = grid.map(|&v| (char::from_digit(v, 10).unwrap(), v as u8));
+            let caption = format!("step {}, {} flashes", k, flashes);
+            if !screen.show(&Scene::new(cells, caption)) {
+                return;
+            }
+            let done = match part {
+                Part::One => k == 100,
+                Part::Two => k > 0 && grid.cells().iter().all(|&v| v == 0),
+            };
+            if done {
+                return;
+            }
+            flashes += step(&mut grid);
+        }
+    }
+}
+
+// Advances the octopuses by one step and returns the number of flashes
+fn step(grid: &mut Grid<u32>) -> usize {
+    let mut pending = vec![];
+    for (p, v) in grid.iter_mut() {
+        *v += 1;
+        if *v == 10 {
+            pending.push(p);
+        }
+    }
+
+    while let Some(p) = pending.pop() {
+        for adj in grid.neighbours8(p) {
+            grid[adj] += 1;
+            if grid[adj] == 10 {
+                pending.push(adj);
+            }
+        }
+    }
+
+    let mut count = 0;
+    for (_, v) in grid.iter_mut() {
+        if *v >= 10 {
+            *v = 0;
+            count += 1;
+        }
+    }
+    count
+}
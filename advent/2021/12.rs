@@ -0,0 +1,77 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc::parse::{self, ParseResult};
+use aoc::{Solution, Value};
+
+pub struct Day12;
+
+type Caves<'a> = HashMap<&'a str, Vec<&'a str>>;
+
+impl Solution for Day12 {
+    type Input<'a> = Caves<'a>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        let mut adjs = HashMap::new();
+        for line in input.lines() {
+            let (u, v) = parse::split_once(line, "-")?;
+
+            adjs.entry(u).or_insert(vec![]).push(v);
+            adjs.entry(v).or_insert(vec![]).push(u);
+        }
+        Ok(adjs)
+    }
+
+    fn part1(adjs: &Self::Input<'_>) -> impl Into<Value> {
+        let mut count = 0;
+        let mut pending = vec![];
+        pending.push(("start", HashSet::new()));
+
+        while let Some((u, s)) = pending.pop() {
+            for v in adjs[u].iter() {
+                match *v {
+                    "start" => (),
+                    "end" => count += 1,
+                    v if v.chars().all(char::is_lowercase) => {
+                        if !s.contains(v) {
+                            let mut ss = s.clone();
+                            ss.insert(v);
+                            pending.push((v, ss));
+                        }
+                    }
+                    v => pending.push((v, s.clone())),
+                };
+            }
+        }
+        count
+    }
+
+    fn part2(adjs: &Self::Input<'_>) -> impl Into<Value> {
+        let mut count = 0;
+        let mut pending = vec![];
+        pending.push(("start", HashMap::new()));
+
+        while let Some((u, s)) = pending.pop() {
+            for v in adjs[u].iter() {
+                match *v {
+                    "start" => (),
+                    "end" => count += 1,
+                    v if v.chars().all(char::is_lowercase) => match s.get(&v).unwrap_or(&0) {
+                        0 => {
+                            let mut ss = s.clone();
+                            ss.insert(v, 1);
+                            pending.push((v, ss));
+                        }
+                        1 if s.values().all(|c| *c < 2) => {
+                            let mut ss = s.clone();
+                            ss.insert(v, 2);
+                            pending.push((v, ss));
+                        }
+                        _ => (),
+                    },
+                    v => pending.push((v, s.clone())),
+                };
+            }
+        }
+        count
+    }
+}
@@ -0,0 +1,60 @@
+use std::collections::HashSet;
+
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{ocr, Point, Solution, Value};
+
+pub struct Day13;
+
+type Dots = HashSet<(usize, usize)>;
+
+impl Solution for Day13 {
+    type Input<'a> = (Dots, Vec<(char, usize)>);
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        let mut sections = parse::sections(input.lines());
+        let p = sections
+            .next()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|l| parse::scan(l, "{},{}"))
+            .collect::<ParseResult<_>>()?;
+
+        let folds = sections
+            .next()
+            .ok_or_else(|| ParseError::after(input, "fold instructions"))?
+            .into_iter()
+            .map(|l| {
+                let (d, n): (&str, _) = parse::scan(l, "fold along {}={}")?;
+                let d = match d {
+                    "x" => 'x',
+                    "y" => 'y',
+                    _ => return Err(ParseError::new(d, "x or y")),
+                };
+                Ok((d, n))
+            })
+            .collect::<ParseResult<_>>()?;
+        Ok((p, folds))
+    }
+
+    fn part1((p, folds): &Self::Input<'_>) -> impl Into<Value> {
+        fold(p.clone(), folds[0]).len()
+    }
+
+    fn part2((p, folds): &Self::Input<'_>) -> impl Into<Value> {
+        let p = folds.iter().fold(p.clone(), |p, &f| fold(p, f));
+
+        // the folded dots spell out eight capital letters
+        let dots = p.iter().map(|&(x, y)| Point::new(x as i64, y as i64));
+        ocr::read_points(dots)
+    }
+}
+
+fn fold(p: Dots, (d, n): (char, usize)) -> Dots {
+    p.into_iter()
+        .map(|(x, y)| match d {
+            'x' if x > n => (n * 2 - x, y),
+            'y' if y > n => (x, n * 2 - y),
+            _ => (x, y),
+        })
+        .collect()
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{Solution, Value};
+
+pub struct Day14;
+
+type Rules = HashMap<(char, char), char>;
+
+impl Solution for Day14 {
+    type Input<'a> = (Vec<char>, Rules);
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        let mut lines = input.lines();
+
+        let template = parse::next(&mut lines, input, "the polymer template")?;
+        if template.is_empty() {
+            return Err(ParseError::new(template, "the polymer template"));
+        }
+        let template = template.chars().collect::<Vec<_>>();
+        lines.next();
+
+        let rules = lines
+            .map(|l| {
+                let (u, v) = parse::split_once(l, " -> ")?;
+                let &[a, b] = &u.chars().collect::<Vec<_>>()[..] else {
+                    return Err(ParseError::new(u, "a pair of elements"));
+                };
+                let &[c] = &v.chars().collect::<Vec<_>>()[..] else {
+                    return Err(ParseError::new(v, "a single element"));
+                };
+                Ok(((a, b), c))
+            })
+            .collect::<ParseResult<HashMap<_, _>>>()?;
+        Ok((template, rules))
+    }
+
+    fn part1((template, rules): &Self::Input<'_>) -> impl Into<Value> {
+        let mut pairs = HashMap::new();
+        for x in template.windows(2) {
+            *pairs.entry((x[0], x[1])).or_insert(0) += 1;
+        }
+
+        for _ in 0..10 {
+            let change = pairs
+                .iter()
+                .filter_map(|(p, n)| rules.get(p).map(|&r| (*p, *n, r)))
+                .collect::<Vec<_>>();
+            for (p, n, r) in change {
+                *pairs.get_mut(&p).unwrap() -= n;
+                *pairs.entry((p.0, r)).or_insert(0) += n;
+                *pairs.entry((r, p.1)).or_insert(0) += n;
+            }
+            pairs.retain(|_, n| *n > 0);
+        }
+
+        element_spread(template, pairs)
+    }
+
+    fn part2((template, rules): &Self::Input<'_>) -> impl Into<Value> {
+        let mut pairs = HashMap::new();
+        for x in template.windows(2) {
+            *pairs.entry((x[0], x[1])).or_insert(0usize) += 1;
+        }
+
+        for _ in 0..40 {
+            pairs = pairs
+                .into_iter()
+                .flat_map(|(p, n)| {
+                    if let Some(t) = rules.get(&p) {
+                        let (l, r) = ((p.0, *t), (*t, p.1));
+                        Vec::from([(l, n), (r, n)])
+                    } else {
+                        Vec::from([(p, n)])
+                    }
+                })
+                .fold(HashMap::new(), |mut h, (p, n)| {
+                    *h.entry(p).or_insert(0usize) += n;
+                    h
+                });
+        }
+
+        element_spread(template, pairs)
+    }
+}
+
+// Every element is counted twice in the pairs, except for both ends of the template
+fn element_spread(template: &[char], pairs: HashMap<(char, char), usize>) -> usize {
+    let mut count = HashMap::new();
+    for (p, n) in pairs.into_iter() {
+        *count.entry(p.0).or_insert(0usize) += n;
+        *count.entry(p.1).or_insert(0usize) += n;
+    }
+
+    *count.entry(template[0]).or_insert(0usize) += 1;
+    *count.entry(template[template.len() - 1]).or_insert(0usize) += 1;
+
+    let count_max = count.values().max().unwrap();
+    let count_min = count.values().min().unwrap();
+
+    (count_max - count_min) / 2
+}
@@ -0,0 +1,46 @@
+use aoc::grid::{Grid, Pos};
+use aoc::parse::ParseResult;
+use aoc::search;
+use aoc::{Solution, Value};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input<'a> = Grid<u32>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        Grid::parse(input, |c| c.to_digit(10).ok_or("a digit"))
+    }
+
+    fn part1(b: &Self::Input<'_>) -> impl Into<Value> {
+        lowest_risk(b)
+    }
+
+    fn part2(tile: &Self::Input<'_>) -> impl Into<Value> {
+        let (r, c) = (tile.rows(), tile.cols());
+        let b = Grid::from_fn(r * 5, c * 5, |(i, j)| {
+            let x = tile[(i % r, j % c)] + (i / r + j / c) as u32;
+            if x > 9 {
+                x - 9
+            } else {
+                x
+            }
+        });
+
+        lowest_risk(&b)
+    }
+}
+
+// every cell costs at least 1, so the manhattan distance to the destination
+// never overestimates the remaining risk
+fn lowest_risk(b: &Grid<u32>) -> u32 {
+    let dest = (b.rows() - 1, b.cols() - 1);
+    search::astar(
+        [(0, 0)],
+        |&pos| b.neighbours4(pos).map(|adj| (adj, b[adj])),
+        |&pos| pos == dest,
+        |&(i, j): &Pos| (dest.0 - i + dest.1 - j) as u32,
+    )
+    .cost()
+    .unwrap()
+}
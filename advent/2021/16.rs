@@ -0,0 +1,126 @@
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{Solution, Value};
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input<'a> = Packet;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        let line = input.lines().next().unwrap_or("");
+        let bits: Vec<bool> = parse::chars(line, |x| x.to_digit(16).ok_or("a hex digit"))?
+            .into_iter()
+            .flat_map(|n| (0..4).rev().map(move |i| n >> i & 1 == 1))
+            .collect();
+
+        match parse_packet(&bits) {
+            Some((_, packet)) => Ok(packet),
+            None => Err(ParseError::new(line, "a complete packet")),
+        }
+    }
+
+    fn part1(packet: &Self::Input<'_>) -> impl Into<Value> {
+        packet.version_sum()
+    }
+
+    fn part2(packet: &Self::Input<'_>) -> impl Into<Value> {
+        packet.eval()
+    }
+}
+
+pub struct Packet {
+    version: usize,
+    kind: Kind,
+}
+
+enum Kind {
+    Literal(usize),
+    Operator(usize, Vec<Packet>),
+}
+
+impl Packet {
+    fn version_sum(&self) -> usize {
+        match &self.kind {
+            Kind::Literal(_) => self.version,
+            Kind::Operator(_, subs) => {
+                self.version + subs.iter().map(Packet::version_sum).sum::<usize>()
+            }
+        }
+    }
+
+    fn eval(&self) -> usize {
+        let subs = match &self.kind {
+            Kind::Literal(v) => return *v,
+            Kind::Operator(_, subs) => subs.iter().map(Packet::eval),
+        };
+        let op: fn(usize, usize) -> usize = match self.kind {
+            Kind::Operator(0, _) => |x, y| x + y,
+            Kind::Operator(1, _) => |x, y| x * y,
+            Kind::Operator(2, _) => |x, y| if x < y { x } else { y },
+            Kind::Operator(3, _) => |x, y| if x > y { x } else { y },
+            Kind::Operator(5, _) => |x, y| (x > y) as usize,
+            Kind::Operator(6, _) => |x, y| (x < y) as usize,
+            Kind::Operator(7, _) => |x, y| (x == y) as usize,
+            _ => panic!("skill issue!"),
+        };
+        subs.reduce(op).unwrap()
+    }
+}
+
+fn parse_bits(bits: &[bool], nbit: usize) -> Option<(&[bool], usize)> {
+    if bits.len() < nbit {
+        return None;
+    }
+    let res = bits[..nbit]
+        .iter()
+        .fold(0, |res, &bit| (res << 1) | bit as usize);
+    Some((&bits[nbit..], res))
+}
+
+fn parse_literal_packet(mut bits: &[bool]) -> Option<(&[bool], usize)> {
+    let mut num = 0;
+    loop {
+        let cont = *bits.first()?;
+        let (b, v) = parse_bits(&bits[1..], 4)?;
+        num = (num << 4) | v;
+        bits = b;
+        if !cont {
+            return Some((bits, num));
+        }
+    }
+}
+
+fn parse_operator_packet(bits: &[bool]) -> Option<(&[bool], Vec<Packet>)> {
+    let (lentype, bits) = bits.split_first()?;
+    let mut subs = Vec::new();
+    if *lentype {
+        let (mut bits, subcnt) = parse_bits(bits, 11)?;
+        for _ in 0..subcnt {
+            let (b, p) = parse_packet(bits)?;
+            subs.push(p);
+            bits = b;
+        }
+        Some((bits, subs))
+    } else {
+        let (bits, len) = parse_bits(bits, 15)?;
+        let (mut sub, bits) = bits.split_at_checked(len)?;
+        while let Some((s, p)) = parse_packet(sub) {
+            subs.push(p);
+            sub = s;
+        }
+        Some((bits, subs))
+    }
+}
+
+fn parse_packet(bits: &[bool]) -> Option<(&[bool], Packet)> {
+    let (bits, version) = parse_bits(bits, 3)?;
+    let (bits, typ) = parse_bits(bits, 3)?;
+    let (bits, kind) = if typ == 4 {
+        let (bits, v) = parse_literal_packet(bits)?;
+        (bits, Kind::Literal(v))
+    } else {
+        let (bits, subs) = parse_operator_packet(bits)?;
+        (bits, Kind::Operator(typ, subs))
+    };
+    Some((bits, Packet { version, kind }))
+}
@@ -1,27 +1,40 @@
 use std::cmp;
-use std::io::{self, BufRead};
 
-fn parse_input() -> ((i32, i32), (i32, i32)) {
-    let line = io::stdin().lock().lines().next().unwrap().unwrap();
-    let line = line.strip_prefix("target area: ").unwrap();
-    let mut spl = line.split(", ");
+use aoc::parse::{self, ParseResult};
+use aoc::{Solution, Value};
 
-    let mut parse_coord = || {
-        // attempt:
-        // > let c = spl.next().unwrap()[2..];
-        // error:
-        // > [E0277]: the size for values of type `str` cannot be known at compilation time
-        // expl:
-        // > str is an immutable sequence of UTF-8 bytes of dynamic length somewhere in memory.
-        //   since the size is unknown, one can only handle it behind a pointer, as `&str`
-        let c = &spl.next().unwrap()[2..];
-        let mut c = c.split("..");
-        let x: i32 = c.next().unwrap().parse().unwrap();
-        let y: i32 = c.next().unwrap().parse().unwrap();
-        (x, y)
-    };
+pub struct Day17;
+
+type Target = ((i32, i32), (i32, i32));
+
+impl Solution for Day17 {
+    type Input<'a> = Target;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn part1(&(_, (ymin, _)): &Self::Input<'_>) -> impl Into<Value> {
+        let vy0 = -ymin - 1;
+        vy0 * (vy0 + 1) / 2
+    }
 
-    (parse_coord(), parse_coord())
+    fn part2(&((xmin, xmax), (ymin, ymax)): &Self::Input<'_>) -> impl Into<Value> {
+        let xabs_max = cmp::max(xmin.abs(), xmax.abs());
+        (ymin..-ymin)
+            .map(|vy| {
+                (-xabs_max..=xabs_max)
+                    .filter(|vx| trajectory_in_target(*vx, vy, xmin, xmax, ymin, ymax))
+                    .count()
+            })
+            .sum::<usize>()
+    }
+}
+
+fn parse_input(input: &str) -> ParseResult<Target> {
+    let line = input.lines().next().unwrap_or("");
+    let (xmin, xmax, ymin, ymax) = parse::scan(line, "target area: x={}..{}, y={}..{}")?;
+    Ok(((xmin, xmax), (ymin, ymax)))
 }
 
 fn trajectory_in_target(vx: i32, vy: i32, xmin: i32, xmax: i32, ymin: i32, ymax: i32) -> bool {
@@ -57,23 +70,3 @@ fn trajectory_in_target(vx: i32, vy: i32, xmin: i32, xmax: i32, ymin: i32, ymax:
         }
     }
 }
-
-fn main() {
-    let ((xmin, xmax), (ymin, ymax)) = parse_input();
-
-    let vy0 = -ymin - 1;
-    println!("Part 1: {:?}", vy0 * (vy0 + 1) / 2);
-
-    let xabs_max = cmp::max(xmin.abs(), xmax.abs());
-    let velocity_count: usize = (ymin..-ymin)
-        .into_iter()
-        .map(|vy| {
-            (-xabs_max..=xabs_max)
-                .into_iter()
-                .filter(|vx| trajectory_in_target(*vx, vy, xmin, xmax, ymin, ymax))
-                .count()
-        })
-        .sum();
-
-    println!("Part 2: {:?}", velocity_count);
-}
@@ -1,5 +1,34 @@
 use std::collections::HashMap;
-use std::io::{self, BufRead};
+
+use aoc::grid::Grid;
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{Solution, Value};
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input<'a> = Img;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        Img::parse(input)
+    }
+
+    fn part1(image: &Self::Input<'_>) -> impl Into<Value> {
+        let mut image = image.clone();
+        for _ in 0..2 {
+            image.enhance();
+        }
+        image.count_lits()
+    }
+
+    fn part2(image: &Self::Input<'_>) -> impl Into<Value> {
+        let mut image = image.clone();
+        for _ in 0..50 {
+            image.enhance();
+        }
+        image.count_lits()
+    }
+}
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Pixel {
@@ -7,12 +36,14 @@ enum Pixel {
     Dark,
 }
 
-impl From<char> for Pixel {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for Pixel {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            '#' => Pixel::Light,
-            '.' => Pixel::Dark,
-            c => panic!("Invalid char {:?}", c),
+            '#' => Ok(Pixel::Light),
+            '.' => Ok(Pixel::Dark),
+            _ => Err("'#' or '.'"),
         }
     }
 }
@@ -26,7 +57,8 @@ impl Pixel {
     }
 }
 
-struct Img {
+#[derive(Clone)]
+pub struct Img {
     algo: Vec<Pixel>,
     pixels: HashMap<(i16, i16), Pixel>,
     min_x: i16,
@@ -37,40 +69,37 @@ struct Img {
 }
 
 impl Img {
-    fn parse<I>(mut lines: I) -> Self
-    where
-        I: Iterator<Item = String>,
-    {
-        let algo: Vec<_> = lines.next().unwrap().chars().map(Pixel::from).collect();
-        lines.next();
-
-        let mut pixels = HashMap::new();
-        let mut max_x = i16::MIN;
-        let mut min_x = i16::MAX;
-        let mut max_y = i16::MIN;
-        let mut min_y = i16::MAX;
-
-        for (y, line) in lines.enumerate() {
-            let y = y as i16;
-            for (x, c) in line.chars().enumerate() {
-                let x = x as i16;
-                max_x = max_x.max(x);
-                max_y = max_y.max(y);
-                min_x = min_x.min(x);
-                min_y = min_y.min(y);
-                pixels.insert((x, y), Pixel::from(c));
+    fn parse(input: &str) -> ParseResult<Self> {
+        let mut sections = parse::sections(input.lines());
+        let (algo, image) = match (sections.next(), sections.next()) {
+            (Some(algo), Some(image)) if algo.len() == 1 => (algo[0], image),
+            _ => {
+                return Err(ParseError::after(
+                    input,
+                    "an algorithm line, a blank line and the image",
+                ))
             }
+        };
+        if algo.chars().count() != 512 {
+            return Err(ParseError::new(algo, "512 pixels of enhancement algorithm"));
         }
+        let algo = parse::chars(algo, Pixel::try_from)?;
+        let image = Grid::parse_lines(image, Pixel::try_from)?;
+
+        let pixels = image
+            .iter()
+            .map(|((y, x), &p)| ((x as i16, y as i16), p))
+            .collect();
 
-        Self {
+        Ok(Self {
             algo,
             pixels,
-            min_x,
-            min_y,
-            max_x,
-            max_y,
+            min_x: 0,
+            min_y: 0,
+            max_x: image.cols() as i16 - 1,
+            max_y: image.rows() as i16 - 1,
             void_state: Pixel::Dark,
-        }
+        })
     }
 
     fn enhance_at(&self, x: i16, y: i16) -> Pixel {
@@ -125,16 +154,3 @@ impl Img {
             .count()
     }
 }
-
-fn main() {
-    let mut lines = io::stdin().lock().lines().map(Result::unwrap);
-    let mut image = Img::parse(&mut lines);
-    for _ in 0..2 {
-        image.enhance();
-    }
-    println!("Part 1: {:?}", image.count_lits());
-    for _ in 0..48 {
-        image.enhance();
-    }
-    println!("Part 2: {:?}", image.count_lits());
-}
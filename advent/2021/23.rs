@@ -1,7 +1,124 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
-use std::io::{self, BufRead};
-use std::time::Instant;
+use aoc::grid::{Grid, Pos};
+use aoc::image::Palette;
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::search::{self, Search};
+use aoc::viz::{Scene, Screen, Visualize};
+use aoc::{Part, Solution, Value};
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    // the rows of the side rooms, top to bottom
+    type Input<'a> = Vec<[Cell; 4]>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        let rooms = input
+            .lines()
+            .skip(2)
+            .take(2)
+            .map(|line| {
+                let mut row = [Cell::E; 4];
+                for (cell, col) in row.iter_mut().zip([3, 5, 7, 9]) {
+                    let c = line
+                        .get(col..col + 1)
+                        .ok_or_else(|| ParseError::after(line, "an amphipod"))?;
+                    *cell = parse::chars(c, Cell::try_from)?[0];
+                }
+                Ok(row)
+            })
+            .collect::<ParseResult<Vec<_>>>()?;
+        if rooms.len() != 2 {
+            return Err(ParseError::after(input, "two rows of side rooms"));
+        }
+        Ok(rooms)
+    }
+
+    fn part1(rooms: &Self::Input<'_>) -> impl Into<Value> {
+        let (init, end) = burrow(rooms);
+        organize(init, &end)
+    }
+
+    fn part2(rooms: &Self::Input<'_>) -> impl Into<Value> {
+        let (init, end) = burrow(&unfolded(rooms));
+        organize(init, &end)
+    }
+}
+
+impl Visualize for Day23 {
+    // walls, floor, then amphipods A to D
+    fn palette() -> Palette {
+        Palette::new(vec![
+            [70, 70, 80],
+            [20, 20, 25],
+            [230, 90, 90],
+            [90, 200, 110],
+            [90, 140, 240],
+            [230, 200, 70],
+        ])
+    }
+
+    // the cheapest way to sort them, a move per step along with the way the
+    // amphipod went
+    fn visualize(rooms: &Self::Input<'_>, part: Part, screen: &mut dyn Screen) {
+        let rooms = match part {
+            Part::One => rooms.clone(),
+            Part::Two => unfolded(rooms),
+        };
+        let (init, end) = burrow(&rooms);
+        let search = cheapest(init, &end);
+        let states = search.path().unwrap();
+
+        let total = states.len() - 1;
+        let mut energy = 0;
+        let first = Scene::new(draw(&init, rooms.len()), format!("move 0/{}", total));
+        if !screen.show(&first) {
+            return;
+        }
+        for (k, pair) in states.windows(2).enumerate() {
+            let (prev, next) = (pair[0], pair[1]);
+            let moved = |from: bool| {
+                (0..23)
+                    .find(|&i| {
+                        (prev.cells[i] == Cell::E) != from && (next.cells[i] == Cell::E) == from
+                    })
+                    .unwrap()
+            };
+            let (orig, dest) = (moved(true), moved(false));
+            let cost = prev.get_move(orig, cell_coord(dest)).unwrap().cost;
+            energy += cost;
+            let caption = format!(
+                "move {}/{}: {} for {}, {} energy",
+                k + 1,
+                total,
+                prev.cells[orig].symbol(),
+                cost,
+                energy
+            );
+            let scene = Scene::new(draw(next, rooms.len()), caption).with_path(route(orig, dest));
+            if !screen.show(&scene) {
+                return;
+            }
+        }
+    }
+}
+
+// The burrow holding `rooms` and the one with every amphipod home.
+fn burrow(rooms: &[[Cell; 4]]) -> (State, State) {
+    use Cell::*;
+
+    let home = vec![[A, B, C, D]; rooms.len()];
+    (State::from_rooms(rooms), State::from_rooms(&home))
+}
+
+// The rooms of part 2, with two more rows folded out of the middle.
+fn unfolded(rooms: &[[Cell; 4]]) -> Vec<[Cell; 4]> {
+    use Cell::*;
+
+    let mut rooms = rooms.to_vec();
+    rooms.insert(1, [D, C, B, A]);
+    rooms.insert(2, [D, B, A, C]);
+    rooms
+}
 
 const CELLS_ROW: [i8; 23] = [
     0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4, 1, 2, 3, 4, 1, 2, 3, 4, 1, 2, 3, 4,
@@ -18,8 +135,8 @@ fn cell_index_at_coord(coord: (i8, i8)) -> Option<usize> {
     (0..23).find(|&i| CELLS_ROW[i] == coord.0 && CELLS_COL[i] == coord.1)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Cell {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cell {
     E = 0,
     A = 1,
     B = 2,
@@ -27,20 +144,32 @@ enum Cell {
     D = 4,
 }
 
-impl From<char> for Cell {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for Cell {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            'A' => Cell::A,
-            'B' => Cell::B,
-            'C' => Cell::C,
-            'D' => Cell::D,
-            '.' => Cell::E,
-            c => panic!("invalid char {:?}", c),
+            'A' => Ok(Cell::A),
+            'B' => Ok(Cell::B),
+            'C' => Ok(Cell::C),
+            'D' => Ok(Cell::D),
+            '.' => Ok(Cell::E),
+            _ => Err("an amphipod or '.'"),
         }
     }
 }
 
 impl Cell {
+    fn symbol(self) -> char {
+        match self {
+            Cell::E => '.',
+            Cell::A => 'A',
+            Cell::B => 'B',
+            Cell::C => 'C',
+            Cell::D => 'D',
+        }
+    }
+
     fn target_column(&self) -> i8 {
         const TARGET_COLS: [i8; 5] = [0, 3, 5, 7, 9];
         match self {
@@ -55,10 +184,9 @@ impl Cell {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 struct State {
-    cells: Vec<Cell>,
-    cost: u32,
+    cells: [Cell; 23],
 }
 
 #[derive(Clone, Debug)]
@@ -69,22 +197,16 @@ struct Move {
 }
 
 impl State {
-    fn parse_grid(grid: Vec<Vec<char>>) -> Self {
-        let room_depth = grid.len() as i8 - 2;
-        let mut cells = vec![Cell::E; 23];
+    fn from_rooms(rooms: &[[Cell; 4]]) -> Self {
+        let room_depth = rooms.len() as i8;
+        let mut cells = [Cell::E; 23];
         for (i, c) in cells.iter_mut().enumerate() {
             let (x, y) = cell_coord(i);
             if x > 0 && x <= room_depth {
-                *c = Cell::from(grid[x as usize][y as usize]);
+                *c = rooms[x as usize - 1][(y as usize - 3) / 2];
             }
         }
-        State { cells, cost: 0 }
-    }
-
-    // Since a cell can be one of 5 values (A, B, C, D, empty), the cells attribute
-    // can be hashed in a base 5 system number
-    fn cells_hash(&self) -> u64 {
-        self.cells.iter().fold(0, |hash, &c| hash * 5 + c as u64)
+        State { cells }
     }
 
     fn amphipod_count(&self) -> usize {
@@ -103,13 +225,10 @@ impl State {
     }
 
     fn apply_move(&self, m: &Move) -> State {
-        let mut cells = self.cells.clone();
+        let mut cells = self.cells;
         cells[m.dest_idx] = cells[m.orig_idx];
         cells[m.orig_idx] = Cell::E;
-        State {
-            cells,
-            cost: self.cost + m.cost,
-        }
+        State { cells }
     }
 
     fn is_hallway_segment_empty(&self, idx: usize, col_a: i8, col_b: i8) -> bool {
@@ -204,113 +323,57 @@ impl State {
         }
         moves
     }
-
-    fn equal_cells_with(&self, other: &Self) -> bool {
-        self.cells
-            .iter()
-            .zip(other.cells.iter())
-            .all(|(a, b)| a == b)
-    }
 }
 
-impl PartialEq for State {
-    fn eq(&self, other: &Self) -> bool {
-        self.cost == other.cost
-    }
+// Returns the least energy needed to get from init to end
+fn organize(init: State, end: &State) -> u32 {
+    cheapest(init, end).cost().unwrap()
 }
 
-impl Eq for State {}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost)
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
+fn cheapest(init: State, end: &State) -> Search<State, u32> {
+    let successors = |state: &State| {
+        state
+            .available_moves()
+            .into_iter()
+            .map(|m| (state.apply_move(&m), m.cost))
+            .collect::<Vec<_>>()
+    };
+    search::dijkstra([init], successors, |state| state == end)
 }
 
-fn dijkstra(init: &State, end: &State) -> Option<(Vec<Move>, u32)> {
-    let mut costs: HashMap<u64, u32> = HashMap::new();
-    let mut prevs: HashMap<u64, (u64, Move)> = HashMap::new();
-
-    let mut queue = BinaryHeap::new();
-    queue.push(init.clone());
-
-    while let Some(state) = queue.pop() {
-        // if end state is reached, trace move path and return total cost
-        if state.equal_cells_with(end) {
-            let mut path = vec![];
-            let init_hash = init.cells_hash();
-            let mut hash = state.cells_hash();
-            while hash != init_hash {
-                let (h, m) = prevs.get(&hash).unwrap();
-                path.push(m.clone());
-                hash = *h;
-            }
-            path.reverse();
-            return Some((path, state.cost));
-        }
-
-        let moves = state.available_moves();
-        for m in moves.iter() {
-            let next = state.apply_move(m);
-            let next_key = next.cells_hash();
-            match costs.get(&next_key) {
-                Some(&c) if next.cost >= c => continue,
-                _ => {
-                    costs.insert(next_key, next.cost);
-                    prevs.insert(next_key, (state.cells_hash(), m.clone()));
-                }
-            };
-            queue.push(next);
+// The burrow as in the puzzle, with rooms `depth` deep.
+fn draw(state: &State, depth: usize) -> Grid<(char, u8)> {
+    let mut lines = vec!["#############", "#...........#", "###.#.#.#.###"];
+    lines.extend(std::iter::repeat_n("  #.#.#.#.#  ", depth - 1));
+    lines.push("  #########  ");
+    let mut grid = Grid::from_fn(lines.len(), 13, |(r, c)| {
+        let c = lines[r].as_bytes()[c] as char;
+        (c, (c != '#') as u8)
+    });
+    for (i, &cell) in state.cells.iter().enumerate() {
+        if cell != Cell::E {
+            grid[cell_pos(i)] = (cell.symbol(), 1 + cell as u8);
         }
     }
-    None
+    grid
 }
 
-fn main() {
-    use Cell::*;
-
-    let mut lines = io::stdin().lock().lines().map(Result::unwrap);
-    lines.next();
-    let grid1: Vec<Vec<_>> = lines.map(|l| l.chars().collect()).collect();
-    let mut grid2 = grid1.clone();
-
-    let init1 = State::parse_grid(grid1);
-    let end1 = State {
-        cells: vec![
-            E, E, E, E, E, E, E, A, A, E, E, B, B, E, E, C, C, E, E, D, D, E, E,
-        ],
-        cost: 0,
-    };
+// Where cell `idx` is drawn, below the top wall.
+fn cell_pos(idx: usize) -> Pos {
+    let (row, col) = cell_coord(idx);
+    (row as usize + 1, col as usize)
+}
 
-    // add 2 more lines for part 2
-    grid2.insert(2, "  #D#C#B#A#".chars().collect());
-    grid2.insert(3, "  #D#B#A#C#".chars().collect());
-    let init2 = State::parse_grid(grid2);
-    let end2 = State {
-        cells: vec![
-            E, E, E, E, E, E, E, A, A, A, A, B, B, B, B, C, C, C, C, D, D, D, D,
-        ],
-        cost: 0,
+// The tiles an amphipod crosses going from one cell to another: up out of
+// its room, along the hallway and down into the other room.
+fn route(orig: usize, dest: usize) -> Vec<Pos> {
+    let ((r0, c0), (r1, c1)) = (cell_pos(orig), cell_pos(dest));
+    let up = (1..=r0).rev().map(|r| (r, c0));
+    let across: Vec<_> = if c0 < c1 {
+        (c0 + 1..=c1).map(|c| (1, c)).collect()
+    } else {
+        (c1..c0).rev().map(|c| (1, c)).collect()
     };
-
-    let start = Instant::now();
-    let (_p, c) = dijkstra(&init1, &end1).unwrap();
-    let p1_time = start.elapsed();
-    // for m in p.iter() {
-    //     println!("{:?} -> {:?} ", cell_coord(m.orig_idx), cell_coord(m.dest_idx));
-    // }
-    println!("Part 1: {} ({}ms elapsed)", c, p1_time.as_millis());
-    let start = Instant::now();
-    let (_p, c) = dijkstra(&init2, &end2).unwrap();
-    let p2_time = start.elapsed();
-    // for m in p.iter() {
-    //     println!("{:?} -> {:?} ", cell_coord(m.orig_idx), cell_coord(m.dest_idx));
-    // }
-    println!("Part 2: {} ({}ms elapsed)", c, p2_time.as_millis());
+    let down = (2..=r1).map(|r| (r, c1));
+    up.chain(across).chain(down).collect()
 }
@@ -1,123 +1,136 @@
-use std::io::{self, BufRead};
+use std::fmt::{self, Display};
+
+use aoc::cycle;
+use aoc::grid::Grid;
+use aoc::image::Palette;
+use aoc::parse::{ParseError, ParseResult};
+use aoc::viz::{Scene, Screen, Visualize};
+use aoc::{Part, Solution, Value};
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input<'a> = Grid<Loc>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        let board = Grid::parse(input, Loc::try_from)?;
+        if board.rows() <= 1 {
+            return Err(ParseError::after(input, "more than one row"));
+        }
+        Ok(board)
+    }
+
+    // the first step on which no sea cucumber moves, counting from 1
+    fn part1(board: &Self::Input<'_>) -> impl Into<Value> {
+        let (stable, _) = cycle::settle(board.clone(), advance);
+        stable + 1
+    }
+
+    // there is no puzzle for the last star
+    fn part2(_: &Self::Input<'_>) -> impl Into<Value> {
+        "Merry Christmas!"
+    }
+}
+
+impl Visualize for Day25 {
+    fn palette() -> Palette {
+        Palette::new(vec![[20, 30, 60], [250, 160, 40], [80, 200, 120]])
+    }
+
+    // the herds shuffling along until they jam, the same for both parts
+    fn visualize(board: &Self::Input<'_>, _: Part, screen: &mut dyn Screen) {
+        let mut board = board.clone();
+        for step in 0.. {
+            let cells = board.map(|&loc| (loc.symbol(), loc as u8));
+            if !screen.show(&Scene::new(cells, format!("step {}", step))) {
+                return;
+            }
+            let next = advance(&board);
+            if next == board {
+                return;
+            }
+            board = next;
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Loc {
+pub enum Loc {
     Void,
     East,
     South,
 }
+impl TryFrom<char> for Loc {
+    type Error = &'static str;
 
-impl From<char> for Loc {
-    fn from(c: char) -> Self {
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            '.' => Loc::Void,
-            '>' => Loc::East,
-            'v' => Loc::South,
-            c => panic!("invalid char {}", c),
+            '.' => Ok(Loc::Void),
+            '>' => Ok(Loc::East),
+            'v' => Ok(Loc::South),
+            _ => Err("'.', '>' or 'v'"),
         }
     }
 }
 
-struct World {
-    board: Vec<Vec<Loc>>,
-    rows: usize,
-    cols: usize,
-    advance_count: u32,
+impl Loc {
+    fn symbol(self) -> char {
+        match self {
+            Loc::Void => '.',
+            Loc::East => '>',
+            Loc::South => 'v',
+        }
+    }
 }
 
-impl World {
-    fn parse<I>(lines: I) -> Self
-    where
-        I: Iterator<Item = String>,
-    {
-        let board: Vec<Vec<Loc>> = lines.map(|l| l.chars().map(Loc::from).collect()).collect();
-        assert!(board.len() > 1);
-        let (rows, cols) = (board.len(), board[0].len());
-        Self {
-            board,
-            rows,
-            cols,
-            advance_count: 0,
-        }
+impl Display for Loc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol())
     }
+}
 
-    fn advance(&mut self) -> bool {
-        self.advance_count += 1;
-        let mut changed = false;
+fn advance(board: &Grid<Loc>) -> Grid<Loc> {
+    let mut board = board.clone();
+    let (rows, cols) = (board.rows(), board.cols());
 
-        let mut to_void = Vec::new();
-        let mut to_move = Vec::new();
-        for x in 0..self.rows {
-            for y in 0..self.cols {
-                if self.board[x][y] == Loc::East {
-                    let yn = (y + 1) % self.cols;
-                    if self.board[x][yn] == Loc::Void {
-                        to_void.push((x, y));
-                        to_move.push((x, yn));
-                    }
+    let mut to_void = Vec::new();
+    let mut to_move = Vec::new();
+    for x in 0..rows {
+        for y in 0..cols {
+            if board[(x, y)] == Loc::East {
+                let next = board.wrapping_shift((x, y), (0, 1));
+                if board[next] == Loc::Void {
+                    to_void.push((x, y));
+                    to_move.push(next);
                 }
             }
         }
-        if !to_move.is_empty() {
-            changed = true;
-        }
-        for (x, y) in to_void.iter() {
-            self.board[*x][*y] = Loc::Void;
-        }
-        for (x, y) in to_move.iter() {
-            self.board[*x][*y] = Loc::East;
-        }
+    }
+    for &p in to_void.iter() {
+        board[p] = Loc::Void;
+    }
+    for &p in to_move.iter() {
+        board[p] = Loc::East;
+    }
 
-        let mut to_void = Vec::new();
-        let mut to_move = Vec::new();
-        for y in 0..self.cols {
-            for x in 0..self.rows {
-                if self.board[x][y] == Loc::South {
-                    let xn = (x + 1) % self.rows;
-                    if self.board[xn][y] == Loc::Void {
-                        to_void.push((x, y));
-                        to_move.push((xn, y));
-                    }
+    let mut to_void = Vec::new();
+    let mut to_move = Vec::new();
+    for y in 0..cols {
+        for x in 0..rows {
+            if board[(x, y)] == Loc::South {
+                let next = board.wrapping_shift((x, y), (1, 0));
+                if board[next] == Loc::Void {
+                    to_void.push((x, y));
+                    to_move.push(next);
                 }
             }
         }
-        if !to_move.is_empty() {
-            changed = true;
-        }
-        for (x, y) in to_void.iter() {
-            self.board[*x][*y] = Loc::Void;
-        }
-        for (x, y) in to_move.iter() {
-            self.board[*x][*y] = Loc::South;
-        }
-        changed
     }
-
-    #[allow(dead_code)]
-    fn debug(&self) {
-        for r in self.board.iter() {
-            for e in r.iter() {
-                print!(
-                    "{}",
-                    match *e {
-                        Loc::Void => '.',
-                        Loc::East => '>',
-                        Loc::South => 'v',
-                    }
-                );
-            }
-            println!("");
-        }
+    for &p in to_void.iter() {
+        board[p] = Loc::Void;
     }
-}
-
-fn main() {
-    let lines = io::stdin().lock().lines().map(Result::unwrap);
-    let mut world = World::parse(lines);
-    let count = loop {
-        if !world.advance() {
-            break world.advance_count;
-        }
-    };
-    println!("{}", count);
+    for &p in to_move.iter() {
+        board[p] = Loc::South;
+    }
+    board
 }
@@ -0,0 +1,5 @@
+//! 2021 day 1 on its own: `cargo run --bin 2021-01 [<part>...] < input/01.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(1).unwrap().main();
+}
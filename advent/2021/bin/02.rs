@@ -0,0 +1,5 @@
+//! 2021 day 2 on its own: `cargo run --bin 2021-02 [<part>...] < input/02.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(2).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2021 day 3 on its own: `cargo run --bin 2021-03 [<part>...] < input/03.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(3).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2021 day 4 on its own: `cargo run --bin 2021-04 [<part>...] < input/04.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(4).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2021 day 6 on its own: `cargo run --bin 2021-06 [<part>...] < input/06.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(6).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2021 day 7 on its own: `cargo run --bin 2021-07 [<part>...] < input/07.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(7).unwrap().main();
+}
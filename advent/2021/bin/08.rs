@@ -0,0 +1,5 @@
+//! 2021 day 8 on its own: `cargo run --bin 2021-08 [<part>...] < input/08.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(8).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2021 day 9 on its own: `cargo run --bin 2021-09 [<part>...] < input/09.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(9).unwrap().main();
+}
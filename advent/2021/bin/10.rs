@@ -0,0 +1,5 @@
+//! 2021 day 10 on its own: `cargo run --bin 2021-10 [<part>...] < input/10.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(10).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2021 day 11 on its own: `cargo run --bin 2021-11 [<part>...] < input/11.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(11).unwrap().main();
+}
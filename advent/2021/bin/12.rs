@@ -0,0 +1,5 @@
+//! 2021 day 12 on its own: `cargo run --bin 2021-12 [<part>...] < input/12.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(12).unwrap().main();
+}
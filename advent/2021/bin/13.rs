@@ -0,0 +1,5 @@
+//! 2021 day 13 on its own: `cargo run --bin 2021-13 [<part>...] < input/13.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(13).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2021 day 14 on its own: `cargo run --bin 2021-14 [<part>...] < input/14.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(14).unwrap().main();
+}
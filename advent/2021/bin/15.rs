@@ -0,0 +1,5 @@
+//! 2021 day 15 on its own: `cargo run --bin 2021-15 [<part>...] < input/15.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(15).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2021 day 16 on its own: `cargo run --bin 2021-16 [<part>...] < input/16.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(16).unwrap().main();
+}
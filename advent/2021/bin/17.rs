@@ -0,0 +1,5 @@
+//! 2021 day 17 on its own: `cargo run --bin 2021-17 [<part>...] < input/17.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(17).unwrap().main();
+}
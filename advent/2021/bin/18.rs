@@ -0,0 +1,5 @@
+//! 2021 day 18 on its own: `cargo run --bin 2021-18 [<part>...] < input/18.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(18).unwrap().main();
+}
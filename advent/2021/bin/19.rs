@@ -0,0 +1,5 @@
+//! 2021 day 19 on its own: `cargo run --bin 2021-19 [<part>...] < input/19.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(19).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2021 day 20 on its own: `cargo run --bin 2021-20 [<part>...] < input/20.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(20).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2021 day 22 on its own: `cargo run --bin 2021-22 [<part>...] < input/22.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(22).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2021 day 23 on its own: `cargo run --bin 2021-23 [<part>...] < input/23.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(23).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2021 day 25 on its own: `cargo run --bin 2021-25 [<part>...] < input/25.txt`.
+
+fn main() {
+    aoc2021::YEAR.day(25).unwrap().main();
+}
@@ -0,0 +1,80 @@
+//! Advent of Code 2021 solutions.
+
+use aoc::{Day, Year};
+
+#[path = "01.rs"]
+mod day01;
+#[path = "02.rs"]
+mod day02;
+#[path = "03.rs"]
+mod day03;
+#[path = "04.rs"]
+mod day04;
+#[path = "06.rs"]
+mod day06;
+#[path = "07.rs"]
+mod day07;
+#[path = "08.rs"]
+mod day08;
+#[path = "09.rs"]
+mod day09;
+#[path = "10.rs"]
+mod day10;
+#[path = "11.rs"]
+mod day11;
+#[path = "12.rs"]
+mod day12;
+#[path = "13.rs"]
+mod day13;
+#[path = "14.rs"]
+mod day14;
+#[path = "15.rs"]
+mod day15;
+#[path = "16.rs"]
+mod day16;
+#[path = "17.rs"]
+mod day17;
+#[path = "18.rs"]
+mod day18;
+#[path = "19.rs"]
+mod day19;
+#[path = "20.rs"]
+mod day20;
+#[path = "22.rs"]
+mod day22;
+#[path = "23.rs"]
+mod day23;
+#[path = "25.rs"]
+mod day25;
+
+pub const DAYS: &[Day] = &[
+    Day::new::<day01::Day01>(1),
+    Day::new::<day02::Day02>(2),
+    Day::new::<day03::Day03>(3),
+    Day::new::<day04::Day04>(4),
+    Day::new::<day06::Day06>(6),
+    Day::new::<day07::Day07>(7),
+    Day::new::<day08::Day08>(8),
+    Day::new::<day09::Day09>(9),
+    Day::new::<day10::Day10>(10),
+    Day::with_viz::<day11::Day11>(11),
+    Day::new::<day12::Day12>(12),
+    Day::new::<day13::Day13>(13),
+    Day::new::<day14::Day14>(14),
+    Day::new::<day15::Day15>(15),
+    Day::new::<day16::Day16>(16),
+    Day::new::<day17::Day17>(17),
+    Day::new::<day18::Day18>(18),
+    Day::new::<day19::Day19>(19),
+    Day::new::<day20::Day20>(20),
+    Day::new::<day22::Day22>(22),
+    Day::with_viz::<day23::Day23>(23),
+    Day::with_viz::<day25::Day25>(25),
+];
+
+pub const YEAR: Year = Year {
+    year: 2021,
+    days: DAYS,
+    input_dir: concat!(env!("CARGO_MANIFEST_DIR"), "/input"),
+    answers: include_str!("answers.txt"),
+};
@@ -0,0 +1,4 @@
+#[test]
+fn recorded_answers() {
+    aoc::answers::assert_recorded(&aoc2021::YEAR);
+}
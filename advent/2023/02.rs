@@ -1,54 +1,56 @@
-use std::io::{self, BufRead};
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{Solution, Value};
 
-#[derive(Debug, Clone, Copy)]
-struct R((usize, usize, usize));
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input<'a> = Vec<Vec<R>>;
 
-fn main() {
-    let data: Vec<_> = io::stdin()
-        .lock()
-        .lines()
-        .map(|l| {
-            let l = l.unwrap();
-            let (_, l) = l.split_once(": ").unwrap();
-            l.split("; ")
-                .map(|t| {
-                    let mut tmp = (0usize, 0usize, 0usize);
-                    let records = t.split(", ");
-                    for r in records {
-                        let (count, kind) = r.split_once(' ').unwrap();
-                        let count = count.parse::<usize>().unwrap();
-                        match kind {
-                            "red" => tmp.0 += count,
-                            "green" => tmp.1 += count,
-                            "blue" => tmp.2 += count,
-                            _ => (),
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        input
+            .lines()
+            .map(|l| {
+                let (_, l) = parse::split_once(l, ": ")?;
+                l.split("; ")
+                    .map(|t| {
+                        let mut tmp = (0usize, 0usize, 0usize);
+                        let records = t.split(", ");
+                        for r in records {
+                            let (count, kind) = parse::split_once(r, " ")?;
+                            let count = parse::num::<usize>(count)?;
+                            match kind {
+                                "red" => tmp.0 += count,
+                                "green" => tmp.1 += count,
+                                "blue" => tmp.2 += count,
+                                _ => return Err(ParseError::new(kind, "red, green or blue")),
+                            }
                         }
-                    }
-                    R(tmp)
-                })
-                .collect::<Vec<_>>()
-        })
-        .collect();
+                        Ok(R(tmp))
+                    })
+                    .collect::<ParseResult<Vec<_>>>()
+            })
+            .collect()
+    }
 
-    println!("part 1: {:?}", part1(&data));
-    println!("part 2: {:?}", part2(&data));
-}
+    fn part1(data: &Self::Input<'_>) -> impl Into<Value> {
+        data.iter()
+            .enumerate()
+            .filter(|&(_, g)| g.iter().all(|&R(x)| x.0 <= 12 && x.1 <= 13 && x.2 <= 14))
+            .map(|(i, _)| i + 1)
+            .sum::<usize>()
+    }
 
-fn part1(data: &[Vec<R>]) -> usize {
-    data.iter()
-        .enumerate()
-        .filter(|&(_, g)| g.iter().all(|&R(x)| x.0 <= 12 && x.1 <= 13 && x.2 <= 14))
-        .map(|(i, _)| i + 1)
-        .sum()
+    fn part2(data: &Self::Input<'_>) -> impl Into<Value> {
+        data.iter()
+            .map(|g| {
+                let mx = g.iter().fold((0usize, 0usize, 0usize), |v, R(x)| {
+                    (v.0.max(x.0), v.1.max(x.1), v.2.max(x.2))
+                });
+                mx.0 * mx.1 * mx.2
+            })
+            .sum::<usize>()
+    }
 }
 
-fn part2(data: &[Vec<R>]) -> usize {
-    data.iter()
-        .map(|g| {
-            let mx = g.iter().fold((0usize, 0usize, 0usize), |v, R(x)| {
-                (v.0.max(x.0), v.1.max(x.1), v.2.max(x.2))
-            });
-            mx.0 * mx.1 * mx.2
-        })
-        .sum()
-}
+#[derive(Debug, Clone, Copy)]
+pub struct R((usize, usize, usize));
@@ -1,110 +1,92 @@
-use std::io::{self, BufRead};
-use std::iter::zip;
+use aoc::interval::{Interval, IntervalSet};
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{Solution, Value};
+
+pub struct Day05;
 
 #[derive(Debug, Clone, Copy)]
-struct Transform {
+pub struct Transform {
     dst: usize,
     src: usize,
     len: usize,
 }
 
-fn main() {
-    let mut lines = io::stdin().lock().lines().map(Result::unwrap);
-    let seeds = lines.next().unwrap();
-    let seeds = seeds.split(':').nth(1).unwrap().trim();
-    let seeds: Vec<_> = seeds
-        .split_whitespace()
-        .flat_map(str::parse::<usize>)
-        .collect();
-    lines.next();
+impl Solution for Day05 {
+    type Input<'a> = (Vec<usize>, Vec<Vec<Transform>>);
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        let mut sections = parse::sections(input.lines());
+        let seeds = sections
+            .next()
+            .ok_or_else(|| ParseError::after(input, "the seeds"))?;
+        let seeds = parse::strip_prefix(seeds[0], "seeds:")?;
+        let seeds = seeds
+            .split_whitespace()
+            .map(parse::num::<usize>)
+            .collect::<ParseResult<Vec<_>>>()?;
 
-    let mut layers: Vec<Vec<Transform>> = Vec::new();
-    while let Some(layer) = parse_input_map(&mut lines) {
-        layers.push(layer);
+        let layers = sections
+            .with_headers()
+            .map(|(header, lines)| parse_input_map(header, &lines))
+            .collect::<ParseResult<Vec<_>>>()?;
+        Ok((seeds, layers))
     }
 
-    println!("Part 1: {:?}", part1(&seeds, &layers));
-    println!("Part 2: {:?}", part2(&seeds, &layers));
-}
+    fn part1((seeds, layers): &Self::Input<'_>) -> impl Into<Value> {
+        seeds
+            .iter()
+            .map(|&x| {
+                let mut res = x;
+                for layer in layers.iter() {
+                    if let Some(t) = layer.iter().find(|&t| t.source().contains(res)) {
+                        res = res - t.src + t.dst;
+                    }
+                }
+                res
+            })
+            .min()
+            .unwrap()
+    }
 
-fn parse_input_map<I>(mut lines: I) -> Option<Vec<Transform>>
-where
-    I: Iterator<Item = String>,
-{
-    lines.next()?;
-    let mut transforms = Vec::new();
-    for line in lines {
-        if line.is_empty() {
-            break;
+    fn part2((seeds, layers): &Self::Input<'_>) -> impl Into<Value> {
+        // the seed list holds pairs of (start, len) of seed ranges
+        let mut seeds = seeds
+            .chunks_exact(2)
+            .map(|pair| Interval::from_len(pair[0], pair[1]))
+            .collect::<IntervalSet<_>>();
+
+        for layer in layers.iter() {
+            let pieces = layer
+                .iter()
+                .map(|t| (t.source(), t.dst))
+                .collect::<Vec<_>>();
+            seeds = seeds.map_linear(&pieces);
         }
-        let mut nums = line.split_whitespace().flat_map(str::parse::<usize>);
-        transforms.push(Transform {
-            dst: nums.next().unwrap(),
-            src: nums.next().unwrap(),
-            len: nums.next().unwrap(),
-        });
+        seeds.min().unwrap()
     }
-    Some(transforms)
 }
 
-fn part1(seeds: &[usize], layers: &[Vec<Transform>]) -> usize {
-    seeds
-        .iter()
-        .map(|&x| {
-            let mut res = x;
-            for layer in layers.iter() {
-                if let Some(t) = layer.iter().find(|&t| res >= t.src && res < t.src + t.len) {
-                    res = res - t.src + t.dst;
-                }
-            }
-            res
-        })
-        .min()
-        .unwrap()
+impl Transform {
+    fn source(&self) -> Interval<usize> {
+        Interval::from_len(self.src, self.len)
+    }
 }
 
-fn part2(seeds: &[usize], layers: &[Vec<Transform>]) -> usize {
-    // convert seed list elements into pairs of (start, len) of seed range
-    // the seed ranges are replaced for every layer of mapping
-    let mut seed_ranges = zip(seeds.iter().step_by(2), seeds.iter().skip(1).step_by(2))
-        .map(|(&a, &b)| (a, b))
-        .collect::<Vec<_>>();
-
-    for layer in layers.iter() {
-        // for every seed range, find sections that can be mapped in the current layer
-        // the remaining sections are included in the next ranges
-        let mut next_ranges = Vec::new();
-
-        for (start, len) in seed_ranges {
-            let mut start = start;
-            let mut len = len;
-
-            while len > 0 {
-                let mut dist: usize = len;
-                let mut found = false;
-
-                for r in layer.iter() {
-                    if start >= r.src && start < r.src + r.len {
-                        let offset = start - r.src;
-                        let frag_len = len.min(r.len - offset);
-                        next_ranges.push((offset + r.dst, frag_len));
-                        len -= frag_len;
-                        start += frag_len;
-                        found = true;
-                        break;
-                    } else if start < r.src {
-                        dist = dist.min(r.src - start);
-                    }
-                }
-                if !found {
-                    next_ranges.push((start, dist));
-                    len -= dist;
-                    start += dist;
-                }
-            }
-        }
-        seed_ranges = next_ranges;
+fn parse_input_map(header: &str, lines: &[&str]) -> ParseResult<Vec<Transform>> {
+    if !header.ends_with(" map:") {
+        return Err(ParseError::new(header, "a map header"));
     }
-    // the smallest leftmost location index of all resulting ranges
-    seed_ranges.iter().map(|(st, _)| *st).min().unwrap()
+    lines
+        .iter()
+        .map(|line| {
+            let nums = line
+                .split_whitespace()
+                .map(parse::num::<usize>)
+                .collect::<ParseResult<Vec<_>>>()?;
+            let [dst, src, len] = nums[..] else {
+                return Err(ParseError::new(line, "three numbers"));
+            };
+            Ok(Transform { dst, src, len })
+        })
+        .collect()
 }
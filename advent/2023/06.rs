@@ -1,41 +1,44 @@
-use std::io::{self, BufRead};
+use aoc::parse::{self, ParseResult};
+use aoc::{Solution, Value};
 
-fn main() {
-    let mut lines = io::stdin().lock().lines().map(Result::unwrap);
-    let line = lines.next().unwrap();
-    let times = line.split(':').nth(1).unwrap();
-    let times_clone = times;
-    let line = lines.next().unwrap();
-    let records = line.split(':').nth(1).unwrap();
-    let records_clone = records;
+pub struct Day06;
 
-    // part 1
-    let times = times.split_whitespace().flat_map(str::parse::<f64>);
-    let records = records.split_whitespace().flat_map(str::parse::<f64>);
-    let p1_ans: f64 = times
-        .zip(records)
-        .map(|(t, r)| count_ways(t, r))
-        .filter(|&c| c > 0.0)
-        .product();
-    println!("Part 1: {:?}", p1_ans);
+impl Solution for Day06 {
+    type Input<'a> = (Vec<&'a str>, Vec<&'a str>);
 
-    // part 2
-    let time = times_clone
-        .chars()
-        .filter(|c| !c.is_whitespace())
-        .collect::<String>()
-        .parse::<f64>()
-        .unwrap();
-    let record = records_clone
-        .chars()
-        .filter(|c| !c.is_whitespace())
-        .collect::<String>()
-        .parse::<f64>()
-        .unwrap();
-    println!("Part 2: {:?}", count_ways(time, record));
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        let mut lines = input.lines();
+        let mut numbers = |prefix: &str| -> ParseResult<Vec<&str>> {
+            let line = parse::next(&mut lines, input, prefix)?;
+            let nums: Vec<&str> = parse::strip_prefix(line, prefix)?
+                .split_whitespace()
+                .collect();
+            for n in &nums {
+                parse::num::<u64>(n)?;
+            }
+            Ok(nums)
+        };
+        Ok((numbers("Time:")?, numbers("Distance:")?))
+    }
+
+    fn part1((times, records): &Self::Input<'_>) -> impl Into<Value> {
+        let times = times.iter().flat_map(|t| t.parse::<f64>());
+        let records = records.iter().flat_map(|r| r.parse::<f64>());
+        times
+            .zip(records)
+            .map(|(t, r)| count_ways(t, r))
+            .filter(|&c| c > 0)
+            .product::<u64>()
+    }
+
+    fn part2((times, records): &Self::Input<'_>) -> impl Into<Value> {
+        let time = times.concat().parse::<f64>().unwrap();
+        let record = records.concat().parse::<f64>().unwrap();
+        count_ways(time, record)
+    }
 }
 
-fn count_ways(time: f64, record: f64) -> f64 {
+fn count_ways(time: f64, record: f64) -> u64 {
     // The problem is finding range in [0, time] so that every integer x of which
     // satisfies the condition x * (time - x) > record, which leads to solving
     // the quadratic inequality: f(x) = x^2 - time*x + record < 0
@@ -51,9 +54,9 @@ fn count_ways(time: f64, record: f64) -> f64 {
             if r2_strict == r2 {
                 r2_strict -= 1.0;
             }
-            r2_strict - r1_strict + 1.0
+            (r2_strict - r1_strict + 1.0).max(0.0) as u64
         }
-        None => 0.0,
+        None => 0,
     }
 }
 
@@ -1,85 +1,154 @@
-use std::collections::HashMap;
-use std::io::{self, BufRead};
+use std::collections::{HashMap, HashSet};
 
-fn main() {
-    let mut lines = io::stdin().lock().lines().map(Result::unwrap);
-    let instructions = lines.next().unwrap();
-    let instructions: Vec<_> = instructions.bytes().collect();
+use aoc::math;
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{Solution, Value};
 
-    lines.next();
-    let lines: Vec<_> = lines.collect();
-    let network: HashMap<_, _> = lines
-        .iter()
-        .map(|line| {
-            let mut s = line.split('=');
-            let k = s.next().unwrap();
-            let v = s.next().unwrap();
-            let mut v = v[2..v.len() - 1].split(',');
-            (
-                k.trim(),
-                (v.next().unwrap().trim(), v.next().unwrap().trim()),
-            )
-        })
-        .collect();
+pub struct Day08;
 
-    println!("Part 1: {:?}", part1(&instructions, &network));
-    println!("Part 2: {:?}", part2(&instructions, &network));
-}
+type Network<'a> = HashMap<&'a str, (&'a str, &'a str)>;
+
+impl Solution for Day08 {
+    type Input<'a> = (&'a [u8], Network<'a>);
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        let mut lines = input.lines();
+        let instructions = parse::next(&mut lines, input, "the instructions")?;
+        if instructions.is_empty() {
+            return Err(ParseError::new(instructions, "the instructions"));
+        }
+        parse::chars(instructions, |c| match c {
+            'L' | 'R' => Ok(()),
+            _ => Err("L or R"),
+        })?;
+
+        lines.next();
+        let network = lines
+            .map(|line| {
+                let (k, l, r) = parse::scan(line, "{} = ({}, {})")?;
+                Ok((k, (l, r)))
+            })
+            .collect::<ParseResult<HashMap<_, _>>>()?;
+        Ok((instructions.as_bytes(), network))
+    }
+
+    fn part1((instructions, network): &Self::Input<'_>) -> impl Into<Value> {
+        // ZZZ can't be reached once the node and the place in the
+        // instructions repeat
+        let mut seen = HashSet::new();
+        let mut cur = "AAA";
+        for (steps, (i, dir)) in instructions.iter().enumerate().cycle().enumerate() {
+            if cur == "ZZZ" {
+                return Ok(steps);
+            }
+            if !seen.insert((cur, i)) {
+                return Err("there is no way from AAA to ZZZ".to_string());
+            }
+            let &(left, right) = network
+                .get(cur)
+                .ok_or_else(|| format!("there is no node {}", cur))?;
+            cur = if *dir == b'L' { left } else { right };
+        }
+        unreachable!()
+    }
 
-fn part1(instructions: &[u8], network: &HashMap<&str, (&str, &str)>) -> usize {
-    let mut instructions = instructions.iter().cycle();
-    let mut cur = "AAA";
-    let mut steps = 0;
-    while cur != "ZZZ" {
-        cur = match instructions.next() {
-            Some(b'L') => network.get(&cur).unwrap().0,
-            Some(b'R') => network.get(&cur).unwrap().1,
-            _ => continue,
+    // Every ghost ends up walking in a loop, since both the node and the
+    // position in the instructions repeat eventually. So the times it stands
+    // on a Z node are a few before the loop starts plus some that repeat with
+    // the length of the loop, and the ghosts all line up at the earliest time
+    // that is one or the other for each of them.
+    fn part2((instructions, network): &Self::Input<'_>) -> impl Into<Value> {
+        let ghosts = network
+            .keys()
+            .filter(|k| k.ends_with('A'))
+            .map(|k| Ghost::walk(k, instructions, network))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // before the last ghost enters its loop, it can only be one of the
+        // times it stands on a Z node on the way there
+        let Some(last) = ghosts.iter().max_by_key(|g| g.loop_start) else {
+            return Err("there are no nodes ending with A".to_string());
         };
-        steps += 1;
+        if let Some(t) = last
+            .lead_in
+            .iter()
+            .find(|&&t| ghosts.iter().all(|g| g.on_z(t)))
+        {
+            return Ok(*t);
+        }
+
+        // afterwards try every combination of Z nodes in the loops
+        let mut combos = vec![vec![]];
+        for g in ghosts.iter() {
+            combos = combos
+                .into_iter()
+                .flat_map(|c| {
+                    g.in_loop.iter().map(move |&t| {
+                        let mut c = c.clone();
+                        c.push((t as i64, g.loop_len as i64));
+                        c
+                    })
+                })
+                .collect();
+        }
+        combos
+            .into_iter()
+            .filter_map(math::crt)
+            .map(|(x, l)| {
+                let (x, l) = (x as u64, l as u64);
+                x + last.loop_start.saturating_sub(x).div_ceil(l) * l
+            })
+            .min()
+            .ok_or_else(|| "the ghosts never line up".to_string())
     }
-    steps
 }
 
-fn part2(instructions: &[u8], network: &HashMap<&str, (&str, &str)>) -> usize {
-    let mut instructions = instructions.iter().cycle();
-    let all_steps = network.keys().filter(|k| k.ends_with('A')).map(|&k| {
-        let mut cur = k;
-        let mut steps = 0;
-        loop {
-            cur = match instructions.next() {
-                Some(b'L') => network.get(&cur).unwrap().0,
-                Some(b'R') => network.get(&cur).unwrap().1,
-                _ => continue,
-            };
-            steps += 1;
+// When a ghost stands on a node ending with Z
+struct Ghost {
+    lead_in: Vec<u64>,
+    loop_start: u64,
+    in_loop: Vec<u64>,
+    loop_len: u64,
+}
+
+impl Ghost {
+    fn walk<'a>(
+        start: &'a str,
+        instructions: &[u8],
+        network: &Network<'a>,
+    ) -> Result<Ghost, String> {
+        let mut seen = HashMap::new();
+        let mut on_z = vec![];
+        let mut cur = start;
+        for t in 0.. {
+            let i = t as usize % instructions.len();
+            if let Some(&loop_start) = seen.get(&(cur, i)) {
+                let (lead_in, in_loop) = on_z.iter().partition(|&&z| z < loop_start);
+                return Ok(Ghost {
+                    lead_in,
+                    loop_start,
+                    in_loop,
+                    loop_len: t - loop_start,
+                });
+            }
+            seen.insert((cur, i), t);
             if cur.ends_with('Z') {
-                return steps;
+                on_z.push(t);
             }
+            let &(left, right) = network
+                .get(cur)
+                .ok_or_else(|| format!("there is no node {}", cur))?;
+            cur = if instructions[i] == b'L' { left } else { right };
         }
-    });
-    lcm(all_steps)
-}
-
-fn lcm<I>(mut nums: I) -> usize
-where
-    I: Iterator<Item = usize>,
-{
-    let mut lcm = if let Some(num) = nums.next() {
-        num
-    } else {
-        return 0;
-    };
-    for x in nums {
-        lcm = lcm / gcd(lcm, x) * x;
+        unreachable!();
     }
-    lcm
-}
 
-fn gcd(a: usize, b: usize) -> usize {
-    if b == 0 {
-        a
-    } else {
-        gcd(b, a % b)
+    fn on_z(&self, t: u64) -> bool {
+        if t < self.loop_start {
+            self.lead_in.contains(&t)
+        } else {
+            let t = self.loop_start + (t - self.loop_start) % self.loop_len;
+            self.in_loop.contains(&t)
+        }
     }
 }
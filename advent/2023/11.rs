@@ -1,12 +1,38 @@
-use std::io::{self, BufRead};
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{Solution, Value};
 
-fn main() {
-    let grid: Vec<_> = io::stdin().lock().lines().map(Result::unwrap).collect();
-    println!("Part 1: {}", solve(&grid, 1));
-    println!("Part 2: {}", solve(&grid, 999999));
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        let grid: Vec<&str> = input.lines().collect();
+        for line in &grid {
+            parse::chars(line, |c| match c {
+                '#' | '.' => Ok(()),
+                _ => Err("'#' or '.'"),
+            })?;
+            if line.len() != grid[0].len() {
+                return Err(ParseError::new(
+                    line,
+                    format!("a row of {} cells", grid[0].len()),
+                ));
+            }
+        }
+        Ok(grid)
+    }
+
+    fn part1(grid: &Self::Input<'_>) -> impl Into<Value> {
+        solve(grid, 1)
+    }
+
+    fn part2(grid: &Self::Input<'_>) -> impl Into<Value> {
+        solve(grid, 999999)
+    }
 }
 
-fn solve(grid: &[String], expand_rate: usize) -> isize {
+fn solve(grid: &[&str], expand_rate: usize) -> isize {
     let col = grid[0].len();
     let mut rows_after_expansion = Vec::new();
     let mut expand_offset = 0;
@@ -1,52 +1,58 @@
 use std::collections::HashMap;
-use std::io::{self, BufRead};
 use std::iter;
 
-fn main() {
-    let lines = io::stdin().lock().lines().map(Result::unwrap);
-    let data: Vec<_> = lines
-        .map(|line| {
-            let mut line = line.split(' ');
-            let springs: Vec<char> = line.next().unwrap().chars().collect();
-            let groups = line
-                .next()
-                .unwrap()
-                .split(',')
-                .map(|x| x.parse::<usize>().unwrap())
-                .collect();
-            (springs, groups)
-        })
-        .collect();
+use aoc::parse::{self, ParseResult};
+use aoc::{Solution, Value};
 
-    println!("Part 1: {:?}", part1(&data));
-    println!("Part 2: {:?}", part2(&data));
-}
+pub struct Day12;
 
-fn part1(data: &[(Vec<char>, Vec<usize>)]) -> usize {
-    data.iter()
-        .map(|(springs, groups)| calc_arrangements(springs, groups, &mut HashMap::new()))
-        .sum()
-}
+impl Solution for Day12 {
+    type Input<'a> = Vec<(Vec<char>, Vec<usize>)>;
 
-fn part2(data: &[(Vec<char>, Vec<usize>)]) -> usize {
-    let data = data.iter().map(|(springs, groups)| {
-        let (rlen, glen) = (springs.len(), groups.len());
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        input
+            .lines()
+            .map(|line| {
+                let (springs, groups) = parse::split_once(line, " ")?;
+                let springs = parse::chars(springs, |c| match c {
+                    '.' | '#' | '?' => Ok(c),
+                    _ => Err("'.', '#' or '?'"),
+                })?;
+                let groups = groups
+                    .split(',')
+                    .map(parse::num::<usize>)
+                    .collect::<ParseResult<_>>()?;
+                Ok((springs, groups))
+            })
+            .collect()
+    }
 
-        // replace the list of spring conditions with five copies of itself (separated by ?)
-        // >(separated by ?)
-        let springs: Vec<_> = springs
-            .iter()
-            .copied()
-            .chain(iter::once('?'))
-            .cycle()
-            .take(rlen * 5 + 4)
-            .collect();
-        let groups: Vec<_> = groups.iter().cycle().take(glen * 5).copied().collect();
-        (springs, groups)
-    });
+    fn part1(data: &Self::Input<'_>) -> impl Into<Value> {
+        data.iter()
+            .map(|(springs, groups)| calc_arrangements(springs, groups, &mut HashMap::new()))
+            .sum::<usize>()
+    }
 
-    data.map(|(springs, groups)| calc_arrangements(&springs, &groups, &mut HashMap::new()))
-        .sum()
+    fn part2(data: &Self::Input<'_>) -> impl Into<Value> {
+        let data = data.iter().map(|(springs, groups)| {
+            let (rlen, glen) = (springs.len(), groups.len());
+
+            // replace the list of spring conditions with five copies of itself (separated by ?)
+            // >(separated by ?)
+            let springs: Vec<_> = springs
+                .iter()
+                .copied()
+                .chain(iter::once('?'))
+                .cycle()
+                .take(rlen * 5 + 4)
+                .collect();
+            let groups: Vec<_> = groups.iter().cycle().take(glen * 5).copied().collect();
+            (springs, groups)
+        });
+
+        data.map(|(springs, groups)| calc_arrangements(&springs, &groups, &mut HashMap::new()))
+            .sum::<usize>()
+    }
 }
 
 fn calc_arrangements(
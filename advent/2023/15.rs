@@ -1,4 +1,7 @@
-use std::io::{self, BufRead};
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{Solution, Value};
+
+pub struct Day15;
 
 #[derive(Copy, Clone, Debug)]
 struct Lens<'a> {
@@ -8,79 +11,83 @@ struct Lens<'a> {
 }
 
 #[derive(Copy, Clone, Debug)]
-enum Instruction<'a> {
+pub enum Instruction<'a> {
     Set(&'a str, usize),
     Rem(&'a str),
 }
 
-fn main() {
-    let mut lines = io::stdin().lock().lines().map(Result::unwrap);
-    let s = lines.next().unwrap();
-    let s2 = s.clone();
-    println!("Part 1: {:?}", part1(s));
-    println!("Part 2: {:?}", part2(s2));
-}
+impl Solution for Day15 {
+    // every step along with what it tells to do
+    type Input<'a> = Vec<(&'a str, Instruction<'a>)>;
 
-fn hash(key: &str) -> usize {
-    key.chars()
-        .fold(0usize, |h, c| (h + usize::from(c as u8)) * 17 % 256)
-}
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        let line = input.lines().next().unwrap_or("");
+        line.split(',')
+            .map(|p| {
+                let instruction = if let Some((label, focal)) = p.split_once('=') {
+                    Instruction::Set(label, parse::num(focal)?)
+                } else if let Some(label) = p.strip_suffix('-') {
+                    Instruction::Rem(label)
+                } else {
+                    return Err(ParseError::after(p, "'=' or '-'"));
+                };
+                Ok((p, instruction))
+            })
+            .collect()
+    }
 
-fn part1(data: String) -> usize {
-    data.split(',').map(hash).sum()
-}
+    fn part1(steps: &Self::Input<'_>) -> impl Into<Value> {
+        steps.iter().map(|(p, _)| hash(p)).sum::<usize>()
+    }
 
-fn part2(data: String) -> usize {
-    let instructions = data.split(',').map(|p| {
-        if let Some((label, focal)) = p.split_once('=') {
-            let focal = focal.parse::<usize>().unwrap();
-            Instruction::Set(label, focal)
-        } else if let Some((label, _)) = p.split_once('-') {
-            Instruction::Rem(label)
-        } else {
-            unreachable!();
-        }
-    });
+    fn part2(steps: &Self::Input<'_>) -> impl Into<Value> {
+        let instructions = steps.iter().map(|&(_, x)| x);
 
-    let mut buckets: Vec<Vec<Lens>> = vec![Vec::new(); 256];
-    for x in instructions {
-        match x {
-            Instruction::Set(label, focal) => {
-                let bucket_idx = hash(label);
-                if let Some(idx) = buckets[bucket_idx]
-                    .iter()
-                    .position(|&x| x.label == label && !x.removed)
-                {
-                    buckets[bucket_idx][idx].focal = focal;
-                } else {
-                    buckets[bucket_idx].push(Lens {
-                        label,
-                        removed: false,
-                        focal,
-                    });
+        let mut buckets: Vec<Vec<Lens>> = vec![Vec::new(); 256];
+        for x in instructions {
+            match x {
+                Instruction::Set(label, focal) => {
+                    let bucket_idx = hash(label);
+                    if let Some(idx) = buckets[bucket_idx]
+                        .iter()
+                        .position(|&x| x.label == label && !x.removed)
+                    {
+                        buckets[bucket_idx][idx].focal = focal;
+                    } else {
+                        buckets[bucket_idx].push(Lens {
+                            label,
+                            removed: false,
+                            focal,
+                        });
+                    }
                 }
-            }
-            Instruction::Rem(label) => {
-                let bucket_idx = hash(label);
-                if let Some(idx) = buckets[bucket_idx]
-                    .iter()
-                    .position(|&x| x.label == label && !x.removed)
-                {
-                    buckets[bucket_idx][idx].removed = true
+                Instruction::Rem(label) => {
+                    let bucket_idx = hash(label);
+                    if let Some(idx) = buckets[bucket_idx]
+                        .iter()
+                        .position(|&x| x.label == label && !x.removed)
+                    {
+                        buckets[bucket_idx][idx].removed = true
+                    }
                 }
-            }
-        };
-    }
+            };
+        }
 
-    buckets.iter_mut().for_each(|b| b.retain(|&x| !x.removed));
+        buckets.iter_mut().for_each(|b| b.retain(|&x| !x.removed));
 
-    buckets
-        .iter()
-        .enumerate()
-        .map(|(h, b)| {
-            b.iter().enumerate().fold(0usize, |tot, (i, lens)| {
-                tot + (h + 1) * (i + 1) * lens.focal
+        buckets
+            .iter()
+            .enumerate()
+            .map(|(h, b)| {
+                b.iter().enumerate().fold(0usize, |tot, (i, lens)| {
+                    tot + (h + 1) * (i + 1) * lens.focal
+                })
             })
-        })
-        .sum()
+            .sum::<usize>()
+    }
+}
+
+fn hash(key: &str) -> usize {
+    key.chars()
+        .fold(0usize, |h, c| (h + usize::from(c as u8)) * 17 % 256)
 }
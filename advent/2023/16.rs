@@ -1,32 +1,82 @@
-use std::io::{self, BufRead};
+use aoc::geom::Dir;
+use aoc::grid::{Grid, Pos};
+use aoc::image::Palette;
+use aoc::parse::ParseResult;
+use aoc::viz::{Scene, Screen, Visualize};
+use aoc::{Part, Solution, Value};
 
-fn main() {
-    let lines = io::stdin().lock().lines().map(Result::unwrap);
-    let grid: Vec<Vec<_>> = lines.map(|l| l.chars().collect()).collect();
-    println!("Part 1: {:?}", part1(&grid));
-    println!("Part 2: {:?}", part2(&grid));
-}
+pub struct Day16;
 
-#[derive(Copy, Clone, Debug)]
-enum Dir {
-    U,
-    D,
-    L,
-    R,
+impl Solution for Day16 {
+    type Input<'a> = Grid<char>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        Grid::parse(input, |c| match c {
+            '.' | '|' | '-' | '/' | '\\' => Ok(c),
+            _ => Err("'.', '|', '-', '/' or '\\'"),
+        })
+    }
+
+    fn part1(grid: &Self::Input<'_>) -> impl Into<Value> {
+        energize(grid, 0, 0, Dir::R)
+    }
+
+    fn part2(grid: &Self::Input<'_>) -> impl Into<Value> {
+        edge_starts(grid)
+            .map(|(x, y, d)| energize(grid, x, y, d))
+            .max()
+            .unwrap()
+    }
 }
 
-impl Dir {
-    fn to_mask(self) -> u8 {
-        use Dir::*;
-        match self {
-            U => 1,
-            D => 1 << 1,
-            L => 1 << 2,
-            R => 1 << 3,
-        }
+impl Visualize for Day16 {
+    fn palette() -> Palette {
+        Palette::new(vec![
+            [25, 25, 35],
+            [110, 110, 130],
+            [200, 150, 40],
+            [255, 230, 120],
+        ])
+    }
+
+    // the beam spreading from the top left, or for part 2 from the best edge
+    fn visualize(grid: &Self::Input<'_>, part: Part, screen: &mut dyn Screen) {
+        let (x, y, dir) = match part {
+            Part::One => (0, 0, Dir::R),
+            Part::Two => edge_starts(grid)
+                .max_by_key(|&(x, y, d)| energize(grid, x, y, d))
+                .unwrap(),
+        };
+        let mut k = 0;
+        trace(grid, (x, y), dir, |tile_dirs, front| {
+            let cells = Grid::from_fn(grid.rows(), grid.cols(), |p| {
+                let (tile, ds) = (grid[p], tile_dirs[p]);
+                let lit = ds != 0;
+                let c = match Dir::ALL.iter().find(|d| d.mask() == ds) {
+                    Some(d) if tile == '.' => d.arrow(),
+                    None if tile == '.' && lit => char::from_digit(ds.count_ones(), 10).unwrap(),
+                    _ => tile,
+                };
+                (c, (tile != '.') as u8 + 2 * lit as u8)
+            });
+            let energized = tile_dirs.cells().iter().filter(|&&ds| ds != 0).count();
+            let caption = format!("step {}, {} tiles energized", k, energized);
+            k += 1;
+            let path = front.iter().map(|&(p, _)| p).collect();
+            screen.show(&Scene::new(cells, caption).with_path(path))
+        });
     }
 }
 
+// Every tile on the edge with the direction pointing inwards, corners once
+// per direction.
+fn edge_starts(grid: &Grid<char>) -> impl Iterator<Item = (usize, usize, Dir)> {
+    let (row, col) = (grid.rows(), grid.cols());
+    let row_start = (0..row).flat_map(move |r| [(r, 0, Dir::R), (r, col - 1, Dir::L)]);
+    let col_start = (1..col - 1).flat_map(move |c| [(0, c, Dir::D), (row - 1, c, Dir::U)]);
+    row_start.chain(col_start)
+}
+
 #[derive(Copy, Clone, Debug)]
 enum Move {
     Forward(Dir),
@@ -51,50 +101,46 @@ fn next_move(tile: char, dir: Dir) -> Move {
     }
 }
 
-fn part1(grid: &[Vec<char>]) -> usize {
-    energize(grid, 0, 0, Dir::R)
+fn energize(grid: &Grid<char>, x: usize, y: usize, dir: Dir) -> usize {
+    let tile_dirs = trace(grid, (x, y), dir, |_, _| true);
+    tile_dirs.cells().iter().filter(|&&i| i != 0).count()
 }
 
-fn part2(grid: &[Vec<char>]) -> usize {
-    let (row, col) = (grid.len(), grid[0].len());
-    let row_start = (0..row).flat_map(|r| [(r, 0, Dir::R), (r, col - 1, Dir::L)]);
-    let col_start = (1..col - 1).flat_map(|c| [(0, c, Dir::D), (row - 1, c, Dir::U)]);
-    row_start
-        .chain(col_start)
-        .map(|(x, y, d)| energize(grid, x, y, d))
-        .max()
-        .unwrap()
-}
+// The directions the beam crosses each tile in, as masks. The beam advances
+// a step at a time and `wave` sees the tiles so far along with the front of
+// the beam before every step, stopping it by returning false.
+fn trace(
+    grid: &Grid<char>,
+    start: Pos,
+    dir: Dir,
+    mut wave: impl FnMut(&Grid<u8>, &[(Pos, Dir)]) -> bool,
+) -> Grid<u8> {
+    let mut beams = vec![(start, dir)];
+    let mut tile_dirs = Grid::new(grid.rows(), grid.cols(), 0u8);
+    tile_dirs[start] |= dir.mask();
 
-fn energize(grid: &[Vec<char>], x: usize, y: usize, dir: Dir) -> usize {
-    let (row, col) = (grid.len(), grid[0].len());
-    let mut beams = vec![(x, y, dir)];
-    let mut tile_dirs = vec![0u8; row * col];
-    tile_dirs[x * col + y] |= dir.to_mask();
-
-    while let Some((x, y, dir)) = beams.pop() {
-        let mut apply_move = |dir: Dir| {
-            if let Some((x, y)) = match dir {
-                Dir::U => (x > 0).then_some((x - 1, y)),
-                Dir::D => (x < row - 1).then_some((x + 1, y)),
-                Dir::L => (y > 0).then_some((x, y - 1)),
-                Dir::R => (y < col - 1).then_some((x, y + 1)),
-            } {
-                let ds = &mut tile_dirs[x * col + y];
-                if *ds & dir.to_mask() == 0 {
-                    *ds |= dir.to_mask();
-                    beams.push((x, y, dir));
+    let mut next = vec![];
+    while !beams.is_empty() && wave(&tile_dirs, &beams) {
+        for (pos, dir) in beams.drain(..) {
+            let mut apply_move = |dir: Dir| {
+                if let Some(p) = grid.step(pos, dir) {
+                    let ds = &mut tile_dirs[p];
+                    if *ds & dir.mask() == 0 {
+                        *ds |= dir.mask();
+                        next.push((p, dir));
+                    }
                 }
-            }
-        };
+            };
 
-        match next_move(grid[x][y], dir) {
-            Move::Forward(d) => apply_move(d),
-            Move::Split(a, b) => {
-                apply_move(a);
-                apply_move(b);
+            match next_move(grid[pos], dir) {
+                Move::Forward(d) => apply_move(d),
+                Move::Split(a, b) => {
+                    apply_move(a);
+                    apply_move(b);
+                }
             }
         }
+        std::mem::swap(&mut beams, &mut next);
     }
-    tile_dirs.iter().filter(|&&i| i != 0).count()
+    tile_dirs
 }
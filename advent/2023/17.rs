@@ -1,123 +1,75 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashSet};
-use std::io::{self, BufRead};
+use aoc::geom::Dir;
+use aoc::grid::{Grid, Pos};
+use aoc::parse::ParseResult;
+use aoc::search;
+use aoc::{Solution, Value};
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-enum Dir {
-    U,
-    D,
-    L,
-    R,
-}
-
-impl Dir {
-    fn perpendicular_dirs(self) -> Vec<Dir> {
-        use Dir::*;
-        match self {
-            U | D => vec![L, R],
-            L | R => vec![U, D],
-        }
-    }
-}
+pub struct Day17;
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 struct State {
-    pos: (usize, usize),
+    pos: Pos,
     dir: Dir,
     streak_len: usize,
-    cost: usize,
 }
 
-impl PartialEq for State {
-    fn eq(&self, other: &Self) -> bool {
-        self.cost == other.cost
-    }
-}
-
-impl Eq for State {}
+impl Solution for Day17 {
+    type Input<'a> = Grid<usize>;
 
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost)
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        Grid::parse(input, |c| {
+            c.to_digit(10).map(|d| d as usize).ok_or("a digit")
+        })
     }
-}
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    fn part1(grid: &Self::Input<'_>) -> impl Into<Value> {
+        solve(grid, 0, 3)
     }
-}
 
-fn main() {
-    let lines = io::stdin().lock().lines().map(Result::unwrap);
-    let grid: Vec<Vec<usize>> = lines
-        .map(|l| {
-            l.chars()
-                .map(|c| c.to_digit(10).unwrap() as usize)
-                .collect()
-        })
-        .collect();
-    println!("Part 1: {:?}", solve(&grid, 0, 3));
-    println!("Part 2: {:?}", solve(&grid, 4, 10));
+    fn part2(grid: &Self::Input<'_>) -> impl Into<Value> {
+        solve(grid, 4, 10)
+    }
 }
 
-fn solve(grid: &[Vec<usize>], min: usize, max: usize) -> usize {
-    let (row, col) = (grid.len(), grid[0].len());
-    let mut queue = BinaryHeap::new();
-    let mut visited = HashSet::new();
-
-    queue.push(State {
-        pos: (0, 0),
-        dir: Dir::R,
-        streak_len: 0,
-        cost: 0,
-    });
-    queue.push(State {
+fn solve(grid: &Grid<usize>, min: usize, max: usize) -> usize {
+    let dest = (grid.rows() - 1, grid.cols() - 1);
+    let starts = [Dir::R, Dir::D].map(|dir| State {
         pos: (0, 0),
-        dir: Dir::D,
+        dir,
         streak_len: 0,
-        cost: 0,
     });
 
-    while let Some(state) = queue.pop() {
-        let (x, y) = state.pos;
-        if x == row - 1 && y == col - 1 && state.streak_len >= min {
-            return state.cost;
-        }
-        if !visited.insert((x, y, state.dir, state.streak_len)) {
-            continue;
-        }
-
+    let successors = |state: &State| {
         let mut next_dirs = vec![];
         if state.streak_len >= min {
-            next_dirs.extend(state.dir.perpendicular_dirs());
+            next_dirs.extend(state.dir.perpendicular());
         }
         if state.streak_len < max {
             next_dirs.push(state.dir);
         }
 
-        for dir in next_dirs {
-            if let Some((x, y)) = match dir {
-                Dir::U => (x > 0).then_some((x - 1, y)),
-                Dir::D => (x < row - 1).then_some((x + 1, y)),
-                Dir::L => (y > 0).then_some((x, y - 1)),
-                Dir::R => (y < col - 1).then_some((x, y + 1)),
-                _ => None,
-            } {
+        next_dirs
+            .into_iter()
+            .filter_map(|dir| {
+                let pos = grid.step(state.pos, dir)?;
                 let streak_len = if dir == state.dir {
                     state.streak_len + 1
                 } else {
                     1
                 };
+                Some((
+                    State {
+                        pos,
+                        dir,
+                        streak_len,
+                    },
+                    grid[pos],
+                ))
+            })
+            .collect::<Vec<_>>()
+    };
 
-                queue.push(State {
-                    pos: (x, y),
-                    dir: dir,
-                    streak_len,
-                    cost: state.cost + grid[x][y],
-                });
-            }
-        }
-    }
-    unreachable!();
+    search::dijkstra(starts, successors, |s| s.pos == dest && s.streak_len >= min)
+        .cost()
+        .unwrap()
 }
@@ -1,84 +1,78 @@
-use std::io::{self, BufRead};
+use aoc::geom::Dir;
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::polygon::Polygon;
+use aoc::{Solution, Value};
 
-#[derive(Copy, Clone, Debug)]
-enum Dir {
-    U,
-    D,
-    L,
-    R,
-}
+pub struct Day18;
 
 #[derive(Copy, Clone, Debug)]
-struct Instruction {
+pub struct Instruction {
     dir: Dir,
-    num: usize,
+    num: i64,
 }
 
-fn main() {
-    let lines: Vec<_> = io::stdin().lock().lines().map(Result::unwrap).collect();
-    println!("Part 1: {}", part1(&lines));
-    println!("Part 2: {}", part2(&lines));
-}
+impl Solution for Day18 {
+    // every line holds the instruction for part 1 and the one hidden in the
+    // color code for part 2
+    type Input<'a> = Vec<(Instruction, Instruction)>;
 
-fn part1(lines: &[String]) -> isize {
-    let plan = lines.iter().map(|l| {
-        let mut sp = l.split(' ');
-        let dir = sp.next().unwrap().chars().next().unwrap();
-        let dir = match dir {
-            'U' => Dir::U,
-            'D' => Dir::D,
-            'L' => Dir::L,
-            'R' => Dir::R,
-            _ => panic!("invalid char: {:?}", dir),
-        };
-        let num = sp.next().unwrap().parse::<usize>().unwrap();
-        Instruction { dir, num }
-    });
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        input
+            .lines()
+            .map(|l| {
+                let mut sp = l.split(' ');
+                let dir = parse::next(&mut sp, l, "a direction")?;
+                let dir = dir.parse().map_err(|e| ParseError::new(dir, e))?;
+                let num = parse::num(parse::next(&mut sp, l, "a distance")?)?;
 
-    // only correct if the trench edges do not form a self-intersecting loop
-    // so that the depth of all blocks in the trench surface is 1
-    trench_area(plan)
-}
+                let color = parse::next(&mut sp, l, "a color code")?;
+                let hex = parse::strip_prefix(color, "(#")?;
+                let hex = hex
+                    .strip_suffix(')')
+                    .ok_or_else(|| ParseError::new(hex, "6 hex digits and ')'"))?;
+                if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    return Err(ParseError::new(hex, "6 hex digits"));
+                }
+                let hex_num = i64::from_str_radix(&hex[..5], 16)
+                    .map_err(|_| ParseError::new(&hex[..5], "5 hex digits"))?;
+                let hex_dir = hex[5..]
+                    .chars()
+                    .next()
+                    .and_then(Dir::from_digit)
+                    .ok_or_else(|| ParseError::new(&hex[5..], "a direction between 0 and 3"))?;
+                Ok((
+                    Instruction { dir, num },
+                    Instruction {
+                        dir: hex_dir,
+                        num: hex_num,
+                    },
+                ))
+            })
+            .collect()
+    }
 
-fn part2(lines: &[String]) -> isize {
-    let plan = lines.iter().map(|l| {
-        let hex = &l[l.find('#').unwrap() + 1..l.find(')').unwrap()];
-        let num = usize::from_str_radix(&hex[..hex.len() - 1], 16).unwrap();
-        let dir = hex.chars().last().unwrap();
-        let dir = match dir {
-            '0' => Dir::R,
-            '1' => Dir::D,
-            '2' => Dir::L,
-            '3' => Dir::U,
-            _ => panic!("invald digit: {:?}", dir),
-        };
-        Instruction { dir, num }
-    });
+    fn part1(plan: &Self::Input<'_>) -> impl Into<Value> {
+        let plan = plan.iter().map(|&(x, _)| x);
 
-    trench_area(plan)
-}
+        // a self-intersecting loop would dig some blocks twice, so
+        // trench_area refuses those
+        trench_area(plan)
+    }
 
-// shoelace formula can be applied here like day 10's solution
-// here i use green's theorem + pick's theorem
-fn trench_area(plan: impl Iterator<Item = Instruction>) -> isize {
-    let (mut x, mut y) = (0_isize, 0_isize);
-    let mut area = 0_isize;
-    let mut peri = 0_isize;
+    fn part2(plan: &Self::Input<'_>) -> impl Into<Value> {
+        let plan = plan.iter().map(|&(_, x)| x);
 
-    for instr in plan {
-        let num = instr.num as isize;
-        peri += num;
-        let (dx, dy) = match instr.dir {
-            Dir::U => (-num, 0),
-            Dir::D => (num, 0),
-            Dir::L => (0, -num),
-            Dir::R => (0, num),
-        };
-        area += x * dy - y * dx;
-        x += dx;
-        y += dy;
+        trench_area(plan)
     }
-    assert!(x == 0 && y == 0);
+}
 
-    (area.abs() + peri) / 2 + 1
+// the lagoon is the cells on and inside the trench loop, i.e. the lattice
+// points of the polygon it traces
+fn trench_area(plan: impl Iterator<Item = Instruction>) -> Result<u64, String> {
+    let trench = Polygon::from_moves(plan.map(|instr| (instr.dir, instr.num)))
+        .ok_or("the trench doesn't return to where it started")?;
+    if !trench.is_simple() {
+        return Err("the trench crosses itself".to_string());
+    }
+    Ok(trench.lattice_points())
 }
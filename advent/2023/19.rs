@@ -1,89 +1,136 @@
-use std::collections::HashMap;
-use std::io::{self, BufRead};
-
-fn main() {
-    let lines: Vec<_> = io::stdin().lock().lines().map(Result::unwrap).collect();
-    let (flows, ratings) = parse_input(&lines);
-    println!("Part 1: {:?}", part1(&flows, &ratings));
-    println!("Part 2: {:?}", part2(&flows));
+use std::collections::{HashMap, HashSet};
+
+use aoc::interval::Interval;
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{Solution, Value};
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input<'a> = (WorkflowMap<'a>, Vec<Vec<usize>>);
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn part1((flows, ratings): &Self::Input<'_>) -> impl Into<Value> {
+        ratings
+            .iter()
+            .flat_map(|n| {
+                (check_rating(flows, &Expr::Redirect("in"), n)).then_some(n.iter().sum::<usize>())
+            })
+            .sum::<usize>()
+    }
+
+    fn part2((flows, _): &Self::Input<'_>) -> impl Into<Value> {
+        let init_expr = Expr::Redirect("in");
+        count_accepted_ratings(flows, &init_expr, [Interval::new(1, 4001); 4])
+    }
 }
 
 #[derive(Clone, Debug)]
-enum Expr<'a> {
+pub enum Expr<'a> {
     Accepted,
     Rejected,
     Redirect(&'a str),
 }
 
-impl<'a> From<&'a str> for Expr<'a> {
-    fn from(s: &'a str) -> Self {
+impl<'a> Expr<'a> {
+    fn parse(s: &'a str) -> ParseResult<Self> {
         match s {
-            "A" => Expr::Accepted,
-            "R" => Expr::Rejected,
-            s if !s.is_empty() => Expr::Redirect(s),
-            _ => panic!("empty workflow label"),
+            "A" => Ok(Expr::Accepted),
+            "R" => Ok(Expr::Rejected),
+            s if !s.is_empty() => Ok(Expr::Redirect(s)),
+            _ => Err(ParseError::new(s, "a workflow label")),
         }
     }
 }
 
 #[derive(Clone, Debug)]
-enum Switch<'a> {
+pub enum Switch<'a> {
     Less(usize, usize, Expr<'a>),
     More(usize, usize, Expr<'a>),
     Dflt(Expr<'a>),
 }
 
 impl<'a> Switch<'a> {
-    fn parse(s: &'a str) -> Self {
+    fn parse(s: &'a str) -> ParseResult<Self> {
         if let Some((cond, expr)) = s.split_once(':') {
-            let var_idx = |c: &str| -> usize {
-                match c {
-                    "x" => 0,
-                    "m" => 1,
-                    "a" => 2,
-                    "s" => 3,
-                    _ => panic!("invalid variable {:?}", c),
-                }
-            };
-            let expr = Expr::from(expr);
+            let expr = Expr::parse(expr)?;
             if let Some((var, val)) = cond.split_once('<') {
-                Switch::Less(var_idx(var), val.parse().unwrap(), expr)
+                Ok(Switch::Less(var_idx(var)?, parse::num(val)?, expr))
             } else if let Some((var, val)) = cond.split_once('>') {
-                Switch::More(var_idx(var), val.parse().unwrap(), expr)
+                Ok(Switch::More(var_idx(var)?, parse::num(val)?, expr))
             } else {
-                unreachable!();
+                Err(ParseError::after(
+                    cond.get(..1).unwrap_or(cond),
+                    "'<' or '>'",
+                ))
             }
         } else {
-            Switch::Dflt(Expr::from(s))
+            Ok(Switch::Dflt(Expr::parse(s)?))
         }
     }
 }
 
 type WorkflowMap<'a> = HashMap<&'a str, Vec<Switch<'a>>>;
 
-fn parse_input(lines: &[String]) -> (WorkflowMap, Vec<Vec<usize>>) {
-    let mut flows = HashMap::new();
-    let mut lines = lines.iter();
-    loop {
-        let line = lines.next().unwrap();
-        if line.is_empty() {
-            break;
+fn var_idx(c: &str) -> ParseResult<usize> {
+    match c {
+        "x" => Ok(0),
+        "m" => Ok(1),
+        "a" => Ok(2),
+        "s" => Ok(3),
+        _ => Err(ParseError::new(c, "x, m, a or s")),
+    }
+}
+
+fn parse_input(input: &str) -> ParseResult<(WorkflowMap<'_>, Vec<Vec<usize>>)> {
+    let mut sections = parse::sections(input.lines());
+    let mut flows = vec![];
+    for line in sections.next().unwrap_or_default() {
+        let (label, flow): (&str, &str) = parse::scan(line, "{}{{{}}}")?;
+        let flow = flow
+            .split(',')
+            .map(Switch::parse)
+            .collect::<ParseResult<Vec<_>>>()?;
+        flows.push((label, flow));
+    }
+
+    // every workflow has to end up accepting or rejecting each part
+    let labels: HashSet<_> = flows.iter().map(|&(label, _)| label).collect();
+    if !labels.contains("in") {
+        return Err(ParseError::new(&input[..0], "a workflow named in"));
+    }
+    for (label, flow) in &flows {
+        if !matches!(flow.last(), Some(Switch::Dflt(_))) {
+            return Err(ParseError::new(
+                label,
+                "a workflow ending in a default rule",
+            ));
+        }
+        for switch in flow {
+            let (Switch::Less(_, _, expr) | Switch::More(_, _, expr) | Switch::Dflt(expr)) = switch;
+            if let Expr::Redirect(next) = expr {
+                if !labels.contains(next) {
+                    return Err(ParseError::new(next, "the label of a workflow"));
+                }
+            }
         }
-        let (label, flow) = line.strip_suffix('}').unwrap().split_once('{').unwrap();
-        let flow: Vec<_> = flow.split(',').map(Switch::parse).collect();
-        flows.insert(label, flow);
     }
+    let flows = flows.into_iter().collect();
 
-    let ratings: Vec<_> = lines
+    let ratings = sections
+        .next()
+        .unwrap_or_default()
+        .into_iter()
         .map(|line| {
-            let line = line.strip_prefix('{').unwrap().strip_suffix('}').unwrap();
-            line.split(',')
-                .map(|s| s[2..].parse::<usize>().unwrap())
-                .collect::<Vec<_>>()
+            let ratings: [usize; 4] = parse::scan(line, "{{x={},m={},a={},s={}}}")?;
+            Ok(ratings.to_vec())
         })
-        .collect();
+        .collect::<ParseResult<Vec<_>>>()?;
 
-    (flows, ratings)
+    Ok((flows, ratings))
 }
 
 fn check_rating<'a>(flows: &WorkflowMap<'a>, expr: &Expr<'a>, rating: &[usize]) -> bool {
@@ -93,8 +140,7 @@ fn check_rating<'a>(flows: &WorkflowMap<'a>, expr: &Expr<'a>, rating: &[usize])
         Expr::Redirect(l) => l,
     };
 
-    let flow = flows.get(label).unwrap();
-    for s in flow {
+    for s in &flows[label] {
         match s {
             Switch::Less(i, v, e) if rating[*i] < *v => return check_rating(flows, e, rating),
             Switch::More(i, v, e) if rating[*i] > *v => return check_rating(flows, e, rating),
@@ -102,28 +148,19 @@ fn check_rating<'a>(flows: &WorkflowMap<'a>, expr: &Expr<'a>, rating: &[usize])
             _ => (),
         }
     }
-    unreachable!();
+    unreachable!("parse_input checked that every workflow has a default rule");
 }
 
-fn part1(flows: &WorkflowMap, ratings: &[Vec<usize>]) -> usize {
-    ratings
-        .iter()
-        .flat_map(|n| {
-            (check_rating(flows, &Expr::Redirect("in"), n)).then_some(n.iter().sum::<usize>())
-        })
-        .sum()
-}
-
-fn count_combinations(ranges: [(usize, usize); 4]) -> usize {
-    ranges.iter().map(|(l, r)| r - l).product()
+fn count_combinations(ranges: [Interval<usize>; 4]) -> usize {
+    ranges.iter().map(Interval::len).product()
 }
 
 fn count_accepted_ratings<'a>(
     flows: &WorkflowMap<'a>,
     expr: &Expr<'a>,
-    mut ranges: [(usize, usize); 4], // copy not reference
+    mut ranges: [Interval<usize>; 4], // copy not reference
 ) -> usize {
-    if ranges.iter().any(|(l, r)| l >= r) {
+    if ranges.iter().any(Interval::is_empty) {
         return 0;
     }
     let label = match expr {
@@ -134,31 +171,28 @@ fn count_accepted_ratings<'a>(
 
     let mut count = 0;
 
-    let flow = flows.get(label).unwrap();
-    for s in flow {
-        match s {
+    for s in &flows[label] {
+        // split off the ratings that match and carry on with the rest
+        let (i, matched, next) = match s {
             Switch::Less(i, val, next) => {
-                let range = ranges[*i];
-                ranges[*i] = (range.0, *val);
-                count += count_accepted_ratings(flows, next, ranges);
-                ranges[*i] = (*val, range.1);
+                let (below, rest) = ranges[*i].split_at(*val);
+                ranges[*i] = rest;
+                (*i, below, next)
             }
             Switch::More(i, val, next) => {
-                let range = ranges[*i];
-                ranges[*i] = (*val + 1, range.1);
-                count += count_accepted_ratings(flows, next, ranges);
-                ranges[*i] = (range.0, *val + 1);
+                let (rest, above) = ranges[*i].split_at(*val + 1);
+                ranges[*i] = rest;
+                (*i, above, next)
             }
             Switch::Dflt(next) => {
                 count += count_accepted_ratings(flows, next, ranges);
+                break;
             }
         };
+        let mut branch = ranges;
+        branch[i] = matched;
+        count += count_accepted_ratings(flows, next, branch);
     }
 
     count
 }
-
-fn part2(flows: &WorkflowMap) -> usize {
-    let init_expr = Expr::Redirect("in");
-    count_accepted_ratings(flows, &init_expr, [(1, 4001); 4])
-}
@@ -1,21 +1,92 @@
 use std::collections::{HashMap, VecDeque};
-use std::io::{self, BufRead};
 
-fn main() {
-    let lines: Vec<_> = io::stdin().lock().lines().map(Result::unwrap).collect();
-    println!("part 1: {}", part1(&lines));
-    println!("part 2: {}", part2(&lines));
+use aoc::math;
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{Solution, Value};
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input<'a> = (ModuleMap<'a>, DestsMap<'a>);
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        parse_input(input)
+    }
+
+    fn part1((module_map, dests_map): &Self::Input<'_>) -> impl Into<Value> {
+        let mut module_map = module_map.clone();
+        let (mut low_count, mut high_count) = (0usize, 0usize);
+
+        for _ in 0..1000 {
+            press_button(&mut module_map, dests_map, |_, _, pulse| {
+                match pulse {
+                    Pulse::High => high_count += 1,
+                    Pulse::Low => low_count += 1,
+                };
+            });
+        }
+
+        low_count * high_count
+    }
+
+    fn part2((module_map, dests_map): &Self::Input<'_>) -> impl Into<Value> {
+        let mut module_map = module_map.clone();
+
+        // input observation: 'rx' is an untyped module linked to a single conjuction module M
+        // and the only way to send a low pulse to 'rx' is to have all incoming pulses to
+        // module M to be high ones
+        let Some((&target_prev, _)) = dests_map.iter().find(|(_, dests)| dests.contains(&"rx"))
+        else {
+            return Err("nothing sends pulses to rx".to_string());
+        };
+
+        let Some(Module::Conj(target_pp)) = module_map.get(target_prev) else {
+            return Err(format!("{} feeding rx isn't a conjunction", target_prev));
+        };
+        let mut history = target_pp
+            .keys()
+            .map(|&n| (n, vec![]))
+            .collect::<HashMap<_, _>>();
+        if history.is_empty() {
+            return Err(format!("nothing sends pulses to {}", target_prev));
+        }
+
+        for round in 0..1_000_000 {
+            press_button(&mut module_map, dests_map, |sender, _, pulse| {
+                if history.contains_key(sender) && matches!(pulse, Pulse::High) {
+                    history.entry(sender).and_modify(|x| x.push(round));
+                }
+            });
+            if history.values().all(|v| v.len() >= 2) {
+                break;
+            }
+        }
+        if let Some((feeder, _)) = history.iter().find(|(_, v)| v.len() < 2) {
+            return Err(format!(
+                "{} didn't send two high pulses in a million presses",
+                feeder
+            ));
+        }
+        // a feeder sends high pulses on its first press and every so many
+        // presses after, so line them up instead of assuming they all start
+        // at press 0
+        let (press, period) = math::crt(history.values().map(|v| (v[0] + 1, v[1] - v[0])))
+            .ok_or("the feeders never line up")?;
+        let (press, period) = (press as u64, period as u64);
+        let earliest = history.values().map(|v| v[0] as u64 + 1).max().unwrap_or(0);
+        Ok(press + earliest.saturating_sub(press).div_ceil(period) * period)
+    }
 }
 
 #[derive(Clone, Copy, Debug, Hash)]
-enum Pulse {
+pub enum Pulse {
     High,
     Low,
 }
 
 // untyped module does not count as one
 #[derive(Clone, Debug)]
-enum Module<'a> {
+pub enum Module<'a> {
     Bcast,
     Flip(bool),
     Conj(HashMap<&'a str, Pulse>),
@@ -24,17 +95,20 @@ enum Module<'a> {
 type ModuleMap<'a> = HashMap<&'a str, Module<'a>>;
 type DestsMap<'a> = HashMap<&'a str, Vec<&'a str>>;
 
-fn parse_input(lines: &[String]) -> (ModuleMap<'_>, DestsMap<'_>) {
+fn parse_input(input: &str) -> ParseResult<(ModuleMap<'_>, DestsMap<'_>)> {
     let mut module_map: ModuleMap<'_> = HashMap::new();
     let mut dests_map: DestsMap<'_> = HashMap::new();
 
-    for s in lines {
-        let (label, dests) = s.split_once(" -> ").unwrap();
-        let (name, module) = match &label[0..1] {
-            "b" => (label, Module::Bcast),
-            "%" => (&label[1..], Module::Flip(false)),
-            "&" => (&label[1..], Module::Conj(HashMap::new())),
-            l => panic!("invalid label format {:?}", l),
+    for s in input.lines() {
+        let (label, dests) = parse::split_once(s, " -> ")?;
+        let (name, module) = if let Some(name) = label.strip_prefix('%') {
+            (name, Module::Flip(false))
+        } else if let Some(name) = label.strip_prefix('&') {
+            (name, Module::Conj(HashMap::new()))
+        } else if label == "broadcaster" {
+            (label, Module::Bcast)
+        } else {
+            return Err(ParseError::new(label, "'%', '&' or broadcaster"));
         };
         module_map.insert(name, module);
         dests_map.insert(name, dests.split(", ").collect());
@@ -51,7 +125,7 @@ fn parse_input(lines: &[String]) -> (ModuleMap<'_>, DestsMap<'_>) {
         }
     }
 
-    (module_map, dests_map)
+    Ok((module_map, dests_map))
 }
 
 fn press_button<'a, F>(module_map: &mut ModuleMap<'a>, dests_map: &DestsMap<'a>, mut extra: F)
@@ -92,73 +166,3 @@ where
         }
     }
 }
-
-fn part1(lines: &[String]) -> usize {
-    let (mut module_map, dests_map) = parse_input(lines);
-    let (mut low_count, mut high_count) = (0, 0);
-
-    for _ in 0..1000 {
-        press_button(&mut module_map, &dests_map, |_, _, pulse| {
-            match pulse {
-                Pulse::High => high_count += 1,
-                Pulse::Low => low_count += 1,
-            };
-        });
-    }
-
-    low_count * high_count
-}
-
-fn part2(lines: &[String]) -> usize {
-    let (mut module_map, dests_map) = parse_input(lines);
-
-    // input observation: 'rx' is an untyped module linked to a single conjuction module M
-    // and the only way to send a low pulse to 'rx' is to have all incoming pulses to
-    // module M to be high ones
-    let target_prev = dests_map
-        .iter()
-        .find(|(_, dests)| dests.contains(&"rx"))
-        .unwrap()
-        .0;
-
-    let mut history = if let Module::Conj(target_pp) = module_map.get(target_prev).unwrap() {
-        target_pp
-            .keys()
-            .map(|&n| (n, vec![]))
-            .collect::<HashMap<_, _>>()
-    } else {
-        unreachable!();
-    };
-
-    for round in 0..1_000_000 {
-        press_button(&mut module_map, &dests_map, |sender, _, pulse| {
-            if history.contains_key(sender) && matches!(pulse, Pulse::High) {
-                history.entry(sender).and_modify(|x| x.push(round));
-            }
-        });
-        if history.values().all(|v| v.len() >= 2) {
-            break;
-        }
-    }
-    lcm(history.values().map(|v| v[1] - v[0]))
-}
-
-fn lcm(mut nums: impl Iterator<Item = usize>) -> usize {
-    let mut lcm = if let Some(num) = nums.next() {
-        num
-    } else {
-        return 0;
-    };
-    for x in nums {
-        lcm = lcm / gcd(lcm, x) * x;
-    }
-    lcm
-}
-
-fn gcd(a: usize, b: usize) -> usize {
-    if b == 0 {
-        a
-    } else {
-        gcd(b, a % b)
-    }
-}
@@ -1,56 +1,74 @@
-use std::io::{self, BufRead};
+use aoc::aabox::AABox;
+use aoc::parse::{self, ParseError, ParseResult};
+use aoc::{Solution, Value};
 
-#[derive(Copy, Clone, Debug)]
-struct Point {
-    x: isize,
-    y: isize,
-    z: isize,
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input<'a> = Vec<Brick>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        let mut bricks = input
+            .lines()
+            .map(Brick::parse)
+            .collect::<ParseResult<Vec<_>>>()?;
+        bricks.sort_by_key(Brick::bottom);
+
+        drop_bricks(&mut bricks);
+        bricks.sort_by_key(Brick::bottom);
+        Ok(bricks)
+    }
+
+    fn part1(bricks: &Self::Input<'_>) -> impl Into<Value> {
+        chain_reactions(bricks).filter(|&x| x == 0).count()
+    }
+
+    fn part2(bricks: &Self::Input<'_>) -> impl Into<Value> {
+        chain_reactions(bricks).sum::<usize>()
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
-struct Brick {
-    lo: Point,
-    hi: Point,
-}
+pub struct Brick(AABox<3>);
 
 impl Brick {
-    fn parse(s: &str) -> Self {
-        let (l, r) = s.split_once('~').unwrap();
-
-        let mut l = l.split(',').map(|v| v.parse::<isize>().unwrap());
-        let l = Point {
-            x: l.next().unwrap(),
-            y: l.next().unwrap(),
-            z: l.next().unwrap(),
-        };
-        let mut r = r.split(',').map(|v| v.parse::<isize>().unwrap());
-        let r = Point {
-            x: r.next().unwrap(),
-            y: r.next().unwrap(),
-            z: r.next().unwrap(),
-        };
+    fn parse(s: &str) -> ParseResult<Self> {
+        let (l, r) = parse::split_once(s, "~")?;
 
-        if l.x != r.x && l.y != r.y && l.z != r.z {
-            panic!("this {:?} a staircase", (l, r));
+        fn point(s: &str) -> ParseResult<[i64; 3]> {
+            let mut sp = s.split(',');
+            let mut coord = |axis| parse::num(parse::next(&mut sp, s, axis)?);
+            Ok([
+                coord("an x coordinate")?,
+                coord("a y coordinate")?,
+                coord("a z coordinate")?,
+            ])
         }
+        let (l, r) = (point(l)?, point(r)?);
 
-        if l.z > r.z || l.x > r.x || l.y > r.y {
-            Brick { lo: r, hi: l }
-        } else {
-            Brick { lo: l, hi: r }
+        if (0..3).all(|i| l[i] != r[i]) {
+            // this is a staircase
+            return Err(ParseError::new(s, "a straight brick"));
         }
+        Ok(Brick(AABox::from_corners(l, r)))
     }
 
+    fn bottom(&self) -> i64 {
+        self.0.min[2]
+    }
+
+    // just above the brick
+    fn top(&self) -> i64 {
+        self.0.max[2]
+    }
+
+    // whether the bricks would hit each other falling straight down
     fn collides(&self, other: &Self) -> bool {
-        (self.lo.x >= other.lo.x && self.lo.x <= other.hi.x)
-            && (other.lo.y >= self.lo.y && other.lo.y <= self.hi.y)
-            || (self.lo.y >= other.lo.y && self.lo.y <= other.hi.y)
-                && (other.lo.x >= self.lo.x && other.lo.x <= self.hi.x)
+        self.0.project([0, 1]).intersects(&other.0.project([0, 1]))
     }
 
-    fn fall_to(&mut self, z: isize) {
-        self.hi.z -= self.lo.z - z;
-        self.lo.z = z;
+    fn fall_to(&mut self, z: i64) {
+        self.0 = self.0.translated([0, 0, z - self.bottom()]);
     }
 }
 
@@ -58,19 +76,19 @@ fn drop_bricks(bricks: &mut [Brick]) -> usize {
     let mut ans = 0;
     for i in 0..bricks.len() {
         let falling = &bricks[i];
-        let z = falling.hi.z;
+        let z = falling.top();
         let z = if let Some(bot) = bricks[..i]
             .iter()
-            .filter(|b| b.hi.z < z && falling.collides(b))
-            .max_by_key(|b| b.hi.z)
+            .filter(|b| b.top() < z && falling.collides(b))
+            .max_by_key(|b| b.top())
         {
-            bot.hi.z + 1
+            bot.top()
         } else {
             1
         };
 
         let falling = &mut bricks[i];
-        if falling.lo.z > z {
+        if falling.bottom() > z {
             falling.fall_to(z);
             ans += 1;
         }
@@ -78,29 +96,12 @@ fn drop_bricks(bricks: &mut [Brick]) -> usize {
     ans
 }
 
-fn main() {
-    let lines = io::stdin().lock().lines().map(Result::unwrap);
-    let mut bricks: Vec<_> = lines.map(|l| Brick::parse(&l)).collect();
-    bricks.sort_by_key(|b| b.lo.z);
-
-    drop_bricks(&mut bricks);
-    bricks.sort_by_key(|b| b.lo.z);
-
+// Number of bricks that would fall after removing each brick
+fn chain_reactions(bricks: &[Brick]) -> impl Iterator<Item = usize> + '_ {
     // nvm bruteforcing is enough
-    let mut destroyed = 0;
-    let mut affected = 0;
-
-    for i in 0..bricks.len() {
-        let mut temp = bricks.clone();
+    (0..bricks.len()).map(|i| {
+        let mut temp = bricks.to_vec();
         temp.remove(i);
-        let x = drop_bricks(&mut temp);
-        if x == 0 {
-            destroyed += 1;
-        } else {
-            affected += x;
-        }
-    }
-
-    println!("Part 1: {:?}", destroyed);
-    println!("Part 2: {:?}", affected);
+        drop_bricks(&mut temp)
+    })
 }
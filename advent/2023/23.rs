@@ -1,65 +1,54 @@
 use std::collections::HashMap;
-use std::io::{self, BufRead};
-
-fn main() {
-    let lines = io::stdin().lock().lines().map(Result::unwrap);
-    let (row, col, mut tiles) = parse_input(lines);
-
-    // part 1
-    println!("Part 1: {:?}", solve(row, col, &tiles));
-    // part 2
-    for (_, v) in tiles.iter_mut() {
-        if matches!(v, Tile::Slope(_)) {
-            *v = Tile::Path;
-        }
+
+use aoc::geom::Dir;
+use aoc::grid::Grid;
+use aoc::parse::ParseResult;
+use aoc::{Solution, Value};
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    // forest tiles are None
+    type Input<'a> = Grid<Option<Tile>>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+        Grid::parse(input, |c| match c {
+            '#' => Ok(None),
+            c => Tile::try_from(c)
+                .map(Some)
+                .map_err(|_| "'#', '.' or a slope"),
+        })
     }
-    println!("Part 2: {:?}", solve(row, col, &tiles));
-}
 
-#[derive(Copy, Clone, Debug)]
-enum Dir {
-    U,
-    D,
-    L,
-    R,
+    fn part1(tiles: &Self::Input<'_>) -> impl Into<Value> {
+        solve(tiles)
+    }
+
+    fn part2(tiles: &Self::Input<'_>) -> impl Into<Value> {
+        let tiles = tiles.map(|t| t.map(|_| Tile::Path));
+        solve(&tiles)
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
-enum Tile {
+pub enum Tile {
     Path,
     Slope(Dir),
 }
 
-impl From<char> for Tile {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for Tile {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            '.' => Tile::Path,
-            '^' => Tile::Slope(Dir::U),
-            'v' => Tile::Slope(Dir::D),
-            '<' => Tile::Slope(Dir::L),
-            '>' => Tile::Slope(Dir::R),
-            _ => panic!("invalid tile {:?}", c),
+            '.' => Ok(Tile::Path),
+            c => Dir::from_arrow(c).map(Tile::Slope).ok_or("'.' or a slope"),
         }
     }
 }
 
 type Point = (usize, usize);
 
-fn parse_input(lines: impl Iterator<Item = String>) -> (usize, usize, HashMap<Point, Tile>) {
-    let grid: Vec<_> = lines.map(|l| l.chars().collect::<Vec<_>>()).collect();
-    let (row, col) = (grid.len(), grid[0].len());
-
-    let mut tiles = HashMap::new();
-    for (x, r) in grid.iter().enumerate() {
-        for (y, &c) in r.iter().enumerate() {
-            if c != '#' {
-                tiles.insert((x, y), Tile::from(c));
-            }
-        }
-    }
-    (row, col, tiles)
-}
-
 #[derive(Copy, Clone, Debug)]
 struct Adj {
     point: Point,
@@ -68,38 +57,23 @@ struct Adj {
 
 type Graph = HashMap<Point, Vec<Adj>>;
 
-fn construct_graph(row: usize, col: usize, tiles: &HashMap<Point, Tile>) -> Graph {
+fn construct_graph(tiles: &Grid<Option<Tile>>) -> Graph {
     let mut graph: Graph = HashMap::new();
+    let is_path = |p: &Point| tiles[*p].is_some();
 
-    for &(x, y) in tiles.keys() {
+    for ((x, y), tile) in tiles.iter() {
+        let Some(tile) = tile else { continue };
         let e = graph.entry((x, y)).or_default();
 
-        match tiles.get(&(x, y)).unwrap() {
+        match tile {
             Tile::Path => {
-                for (nx, ny) in [
-                    x.checked_sub(1).map(|x| (x, y)),
-                    (x < row - 1).then_some((x + 1, y)),
-                    y.checked_sub(1).map(|y| (x, y)),
-                    (y < col - 1).then_some((x, y + 1)),
-                ]
-                .iter()
-                .flatten()
-                .filter(|p| tiles.contains_key(p))
-                {
-                    e.push(Adj {
-                        point: (*nx, *ny),
-                        distance: 1,
-                    });
+                for point in tiles.neighbours4((x, y)).filter(is_path) {
+                    e.push(Adj { point, distance: 1 });
                 }
             }
             Tile::Slope(dir) => {
-                if let Some((nx, ny)) = match dir {
-                    Dir::U => x.checked_sub(1).map(|x| (x, y)),
-                    Dir::D => (x < row - 1).then_some((x + 1, y)),
-                    Dir::L => y.checked_sub(1).map(|y| (x, y)),
-                    Dir::R => (y < col - 1).then_some((x, y + 1)),
-                } {
-                    if tiles.contains_key(&(nx, ny)) {
+                if let Some((nx, ny)) = tiles.step((x, y), *dir) {
+                    if is_path(&(nx, ny)) {
                         e.push(Adj {
                             point: (nx, ny),
                             distance: 1,
@@ -161,10 +135,10 @@ fn dfs(
     max_distance
 }
 
-fn solve(row: usize, col: usize, tiles: &HashMap<Point, Tile>) -> usize {
-    let graph = construct_graph(row, col, tiles);
+fn solve(tiles: &Grid<Option<Tile>>) -> usize {
+    let graph = construct_graph(tiles);
     let start = graph.keys().find(|&p| p.0 == 0).unwrap();
-    let end = graph.keys().find(|&p| p.0 == row - 1).unwrap();
+    let end = graph.keys().find(|&p| p.0 == tiles.rows() - 1).unwrap();
 
     dfs(&graph, *start, *end, &mut HashMap::new()).unwrap()
 }
@@ -0,0 +1,5 @@
+//! 2023 day 2 on its own: `cargo run --bin 2023-02 [<part>...] < input/02.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(2).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2023 day 4 on its own: `cargo run --bin 2023-04 [<part>...] < input/04.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(4).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2023 day 5 on its own: `cargo run --bin 2023-05 [<part>...] < input/05.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(5).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2023 day 6 on its own: `cargo run --bin 2023-06 [<part>...] < input/06.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(6).unwrap().main();
+}
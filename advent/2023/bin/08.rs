@@ -0,0 +1,5 @@
+//! 2023 day 8 on its own: `cargo run --bin 2023-08 [<part>...] < input/08.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(8).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2023 day 11 on its own: `cargo run --bin 2023-11 [<part>...] < input/11.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(11).unwrap().main();
+}
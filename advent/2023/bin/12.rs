@@ -0,0 +1,5 @@
+//! 2023 day 12 on its own: `cargo run --bin 2023-12 [<part>...] < input/12.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(12).unwrap().main();
+}
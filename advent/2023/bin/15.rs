@@ -0,0 +1,5 @@
+//! 2023 day 15 on its own: `cargo run --bin 2023-15 [<part>...] < input/15.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(15).unwrap().main();
+}
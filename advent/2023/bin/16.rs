@@ -0,0 +1,5 @@
+//! 2023 day 16 on its own: `cargo run --bin 2023-16 [<part>...] < input/16.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(16).unwrap().main();
+}
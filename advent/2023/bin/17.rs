@@ -0,0 +1,5 @@
+//! 2023 day 17 on its own: `cargo run --bin 2023-17 [<part>...] < input/17.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(17).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2023 day 18 on its own: `cargo run --bin 2023-18 [<part>...] < input/18.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(18).unwrap().main();
+}
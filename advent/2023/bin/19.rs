@@ -0,0 +1,5 @@
+//! 2023 day 19 on its own: `cargo run --bin 2023-19 [<part>...] < input/19.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(19).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2023 day 20 on its own: `cargo run --bin 2023-20 [<part>...] < input/20.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(20).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2023 day 21 on its own: `cargo run --bin 2023-21 [<part>...] < input/21.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(21).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2023 day 22 on its own: `cargo run --bin 2023-22 [<part>...] < input/22.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(22).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2023 day 23 on its own: `cargo run --bin 2023-23 [<part>...] < input/23.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(23).unwrap().main();
+}
@@ -0,0 +1,5 @@
+//! 2023 day 24 on its own: `cargo run --bin 2023-24 [<part>...] < input/24.txt`.
+
+fn main() {
+    aoc2023::YEAR.day(24).unwrap().main();
+}
@@ -0,0 +1,65 @@
+//! Advent of Code 2023 solutions.
+
+use aoc::{Day, Year};
+
+#[path = "02.rs"]
+mod day02;
+#[path = "04.rs"]
+mod day04;
+#[path = "05.rs"]
+mod day05;
+#[path = "06.rs"]
+mod day06;
+#[path = "08.rs"]
+mod day08;
+#[path = "11.rs"]
+mod day11;
+#[path = "12.rs"]
+mod day12;
+#[path = "15.rs"]
+mod day15;
+#[path = "16.rs"]
+mod day16;
+#[path = "17.rs"]
+mod day17;
+#[path = "18.rs"]
+mod day18;
+#[path = "19.rs"]
+mod day19;
+#[path = "20.rs"]
+mod day20;
+#[path = "21.rs"]
+mod day21;
+#[path = "22.rs"]
+mod day22;
+#[path = "23.rs"]
+mod day23;
+#[path = "24.rs"]
+mod day24;
+
+pub const DAYS: &[Day] = &[
+    Day::new::<day02::Day02>(2),
+    Day::new::<day04::Day04>(4),
+    Day::new::<day05::Day05>(5),
+    Day::new::<day06::Day06>(6),
+    Day::new::<day08::Day08>(8),
+    Day::new::<day11::Day11>(11),
+    Day::new::<day12::Day12>(12),
+    Day::new::<day15::Day15>(15),
+    Day::with_viz::<day16::Day16>(16),
+    Day::new::<day17::Day17>(17),
+    Day::new::<day18::Day18>(18),
+    Day::new::<day19::Day19>(19),
+    Day::new::<day20::Day20>(20),
+    Day::new::<day21::Day21>(21),
+    Day::new::<day22::Day22>(22),
+    Day::new::<day23::Day23>(23),
+    Day::new::<day24::Day24>(24),
+];
+
+pub const YEAR: Year = Year {
+    year: 2023,
+    days: DAYS,
+    input_dir: concat!(env!("CARGO_MANIFEST_DIR"), "/input"),
+    answers: include_str!("answers.txt"),
+};
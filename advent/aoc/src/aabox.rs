@@ -0,0 +1,263 @@
+//! Axis-aligned boxes in any number of dimensions, and unions of them.
+//!
+//! Boxes are half-open like [`Interval`]s: a box covers the integer points
+//! `p` with `min[i] <= p[i] < max[i]` on every axis, so the cubes of a
+//! puzzle's inclusive `x=10..12` range span `10..13`, see
+//! [`AABox::from_corners`].
+//!
+//! [`Interval`]: crate::interval::Interval
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AABox<const N: usize> {
+    pub min: [i64; N],
+    pub max: [i64; N],
+}
+
+impl<const N: usize> AABox<N> {
+    pub const fn new(min: [i64; N], max: [i64; N]) -> AABox<N> {
+        AABox { min, max }
+    }
+
+    /// The box spanning two opposite corner cubes, both included, given in
+    /// any order.
+    pub fn from_corners(a: [i64; N], b: [i64; N]) -> AABox<N> {
+        AABox {
+            min: std::array::from_fn(|i| a[i].min(b[i])),
+            max: std::array::from_fn(|i| a[i].max(b[i]) + 1),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..N).any(|i| self.min[i] >= self.max[i])
+    }
+
+    /// The number of points in the box, 0 if it's empty.
+    pub fn volume(&self) -> i64 {
+        (0..N).map(|i| (self.max[i] - self.min[i]).max(0)).product()
+    }
+
+    pub fn contains(&self, p: [i64; N]) -> bool {
+        (0..N).all(|i| self.min[i] <= p[i] && p[i] < self.max[i])
+    }
+
+    /// The points in both boxes, `None` if there are none.
+    pub fn intersection(&self, other: &AABox<N>) -> Option<AABox<N>> {
+        let common = AABox {
+            min: std::array::from_fn(|i| self.min[i].max(other.min[i])),
+            max: std::array::from_fn(|i| self.max[i].min(other.max[i])),
+        };
+        (!common.is_empty()).then_some(common)
+    }
+
+    pub fn intersects(&self, other: &AABox<N>) -> bool {
+        (0..N).all(|i| self.min[i].max(other.min[i]) < self.max[i].min(other.max[i]))
+    }
+
+    /// The points of `self` outside `other`, as at most `2 * N` disjoint
+    /// boxes.
+    pub fn subtract(&self, other: &AABox<N>) -> Vec<AABox<N>> {
+        let Some(cut) = self.intersection(other) else {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        };
+        // peel off the slabs below and above the cut one axis at a time,
+        // narrowing what's left to the cut on that axis
+        let mut pieces = vec![];
+        let mut rest = *self;
+        for i in 0..N {
+            if rest.min[i] < cut.min[i] {
+                let mut below = rest;
+                below.max[i] = cut.min[i];
+                pieces.push(below);
+            }
+            if cut.max[i] < rest.max[i] {
+                let mut above = rest;
+                above.min[i] = cut.max[i];
+                pieces.push(above);
+            }
+            rest.min[i] = cut.min[i];
+            rest.max[i] = cut.max[i];
+        }
+        pieces
+    }
+
+    /// The box moved by `delta`.
+    pub fn translated(&self, delta: [i64; N]) -> AABox<N> {
+        AABox {
+            min: std::array::from_fn(|i| self.min[i] + delta[i]),
+            max: std::array::from_fn(|i| self.max[i] + delta[i]),
+        }
+    }
+
+    /// The box seen along the axes not listed, e.g. `project([0, 1])` is the
+    /// footprint of a 3D box on the xy plane.
+    pub fn project<const M: usize>(&self, axes: [usize; M]) -> AABox<M> {
+        AABox {
+            min: axes.map(|i| self.min[i]),
+            max: axes.map(|i| self.max[i]),
+        }
+    }
+}
+
+/// A union of boxes, stored as disjoint boxes: adding a box first cuts it
+/// out of the ones already there, so nothing is counted twice and removing
+/// is the same cut without the adding. Each cut splits a box into at most
+/// `2 * N` pieces and only boxes that actually overlap get cut.
+#[derive(Clone, Debug, Default)]
+pub struct BoxSet<const N: usize> {
+    boxes: Vec<AABox<N>>,
+}
+
+impl<const N: usize> BoxSet<N> {
+    pub fn new() -> BoxSet<N> {
+        BoxSet { boxes: vec![] }
+    }
+
+    /// Disjoint boxes covering the set, in no particular order.
+    pub fn boxes(&self) -> &[AABox<N>] {
+        &self.boxes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boxes.is_empty()
+    }
+
+    pub fn contains(&self, p: [i64; N]) -> bool {
+        self.boxes.iter().any(|b| b.contains(p))
+    }
+
+    pub fn intersects(&self, other: &AABox<N>) -> bool {
+        self.boxes.iter().any(|b| b.intersects(other))
+    }
+
+    pub fn insert(&mut self, b: AABox<N>) {
+        self.remove(&b);
+        if !b.is_empty() {
+            self.boxes.push(b);
+        }
+    }
+
+    pub fn remove(&mut self, b: &AABox<N>) {
+        if !self.intersects(b) {
+            return;
+        }
+        let mut kept = Vec::with_capacity(self.boxes.len());
+        for existing in self.boxes.drain(..) {
+            if existing.intersects(b) {
+                kept.extend(existing.subtract(b));
+            } else {
+                kept.push(existing);
+            }
+        }
+        self.boxes = kept;
+    }
+
+    /// The number of points in the union.
+    pub fn volume(&self) -> i64 {
+        self.boxes.iter().map(AABox::volume).sum()
+    }
+}
+
+impl<const N: usize> FromIterator<AABox<N>> for BoxSet<N> {
+    fn from_iter<I: IntoIterator<Item = AABox<N>>>(iter: I) -> BoxSet<N> {
+        let mut set = BoxSet::new();
+        for b in iter {
+            set.insert(b);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(range: std::ops::Range<i64>) -> impl Iterator<Item = [i64; 3]> {
+        let r = range.clone();
+        r.clone().flat_map(move |x| {
+            let r = r.clone();
+            r.clone()
+                .flat_map(move |y| r.clone().map(move |z| [x, y, z]))
+        })
+    }
+
+    // The pieces are disjoint and cover exactly the points of `a` outside `b`.
+    fn check_subtract(a: AABox<3>, b: AABox<3>) -> Vec<AABox<3>> {
+        let pieces = a.subtract(&b);
+        for p in points(-1..8) {
+            let covering = pieces.iter().filter(|piece| piece.contains(p)).count();
+            let expected = a.contains(p) && !b.contains(p);
+            assert_eq!(covering, expected as usize, "{:?} - {:?} at {:?}", a, b, p);
+        }
+        assert!(pieces.iter().all(|piece| !piece.is_empty()));
+        pieces
+    }
+
+    #[test]
+    fn subtract() {
+        let a = AABox::new([0, 0, 0], [6, 6, 6]);
+        // a hole in the middle leaves a slab on each side
+        let pieces = check_subtract(a, AABox::new([2, 2, 2], [4, 4, 4]));
+        assert_eq!(pieces.len(), 6);
+        assert_eq!(pieces.iter().map(AABox::volume).sum::<i64>(), 216 - 8);
+        // a corner only takes three
+        assert_eq!(check_subtract(a, AABox::new([4, 4, 4], [9, 9, 9])).len(), 3);
+        // a box through the middle along z leaves four
+        assert_eq!(
+            check_subtract(a, AABox::new([2, 2, -1], [4, 4, 9])).len(),
+            4
+        );
+        // nothing in common, or nothing left
+        assert_eq!(check_subtract(a, AABox::new([6, 0, 0], [7, 6, 6])), [a]);
+        assert!(check_subtract(a, AABox::new([-1, -1, -1], [7, 7, 7])).is_empty());
+        assert!(check_subtract(AABox::new([3, 0, 0], [1, 5, 5]), a).is_empty());
+    }
+
+    #[test]
+    fn from_corners_includes_both() {
+        let b = AABox::from_corners([10, -2], [12, -4]);
+        assert_eq!(b, AABox::new([10, -4], [13, -1]));
+        assert_eq!(b.volume(), 9);
+        assert!(b.contains([12, -4]) && !b.contains([13, -4]));
+        assert_eq!(AABox::new([5, 5], [3, 9]).volume(), 0);
+    }
+
+    #[test]
+    fn box_set_matches_brute_force() {
+        // the reboot steps of 2021 day 22, shrunk
+        let steps = [
+            (true, AABox::new([0, 0, 0], [4, 4, 4])),
+            (true, AABox::new([2, 2, 2], [6, 6, 6])),
+            (false, AABox::new([1, 1, 1], [3, 3, 3])),
+            (true, AABox::new([3, 0, 0], [5, 7, 1])),
+            (false, AABox::new([4, 4, -1], [8, 8, 8])),
+            (true, AABox::new([1, 1, 1], [2, 2, 2])),
+            (false, AABox::new([9, 9, 9], [10, 10, 10])),
+        ];
+        let mut set = BoxSet::new();
+        let mut on = std::collections::HashSet::new();
+        for (turn_on, b) in steps {
+            if turn_on {
+                set.insert(b);
+            } else {
+                set.remove(&b);
+            }
+            for p in points(-1..9).filter(|&p| b.contains(p)) {
+                if turn_on {
+                    on.insert(p);
+                } else {
+                    on.remove(&p);
+                }
+            }
+            assert_eq!(set.volume(), on.len() as i64);
+            for p in points(-1..9) {
+                assert_eq!(set.contains(p), on.contains(&p), "{:?}", p);
+            }
+            // the boxes stay disjoint
+            for (i, a) in set.boxes().iter().enumerate() {
+                assert!(set.boxes()[i + 1..].iter().all(|b| !a.intersects(b)));
+            }
+        }
+        let all = steps.iter().filter(|(on, _)| *on).map(|&(_, b)| b);
+        assert_eq!(all.collect::<BoxSet<3>>().volume(), 64 + 64 - 8 + 14 - 4);
+    }
+}
@@ -0,0 +1,154 @@
+//! Recorded answers, used as a regression suite.
+//!
+//! Each year keeps an `answers.txt` next to its sources, one answer per line:
+//!
+//! ```text
+//! # day part variant answer
+//! 01 1 input 1754
+//! 08 2 sample3 6
+//! ```
+//!
+//! Blank lines and `#` comments are ignored. The variant names an input file
+//! as described in [`Variant`].
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::thread;
+
+use crate::input::{self, Variant};
+use crate::{Part, Value, Year};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: u8,
+    pub part: Part,
+    pub variant: Variant,
+    pub answer: String,
+}
+
+/// Parses the contents of an answers file.
+pub fn parse(text: &str) -> Result<Vec<Answer>, String> {
+    let mut answers = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let err = |msg: String| format!("line {}: {}", i + 1, msg);
+        let mut fields = line.splitn(4, char::is_whitespace);
+        let (Some(day), Some(part), Some(variant), Some(answer)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            return Err(err(format!(
+                "expected `day part variant answer`, got {:?}",
+                line
+            )));
+        };
+        answers.push(Answer {
+            day: day
+                .parse()
+                .map_err(|_| err(format!("invalid day {:?}", day)))?,
+            part: part.parse().map_err(err)?,
+            variant: variant.parse().map_err(err)?,
+            answer: answer.trim().to_string(),
+        });
+    }
+    Ok(answers)
+}
+
+/// Runs every solution of `year` against its recorded answers and returns a
+/// description of each mismatch. Input files of a registered day that have no
+/// recorded answer at all are reported too, so new inputs don't go unchecked.
+pub fn check(year: &Year) -> Vec<String> {
+    let answers = match parse(year.answers) {
+        Ok(answers) => answers,
+        Err(e) => return vec![format!("{} answers.txt: {}", year.year, e)],
+    };
+    let dir = Path::new(year.input_dir);
+
+    let mut failures = Vec::new();
+    let mut grouped: BTreeMap<(u8, Variant), Vec<&Answer>> = BTreeMap::new();
+    for answer in &answers {
+        if year.day(answer.day).is_none() {
+            failures.push(format!(
+                "{} day {}: answer recorded but no solution registered",
+                year.year, answer.day
+            ));
+            continue;
+        }
+        grouped
+            .entry((answer.day, answer.variant))
+            .or_default()
+            .push(answer);
+    }
+
+    for day in year.days {
+        for variant in variants(dir, day.day) {
+            if !grouped.contains_key(&(day.day, variant)) {
+                failures.push(format!(
+                    "{} day {} ({}): input present but no answers recorded",
+                    year.year, day.day, variant
+                ));
+            }
+        }
+    }
+
+    // Days are independent and some take a while unoptimized, so run them in
+    // parallel.
+    thread::scope(|s| {
+        let handles: Vec<_> = grouped
+            .iter()
+            .map(|(&(day, variant), expected)| {
+                let handle = s.spawn(move || {
+                    let input = input::read(dir, day, variant).map_err(|e| e.to_string())?;
+                    let parts: Vec<Part> = expected.iter().map(|a| a.part).collect();
+                    let day = year.day(day).unwrap();
+                    day.solve(&input, &parts).map_err(|e| e.to_string())
+                });
+                (day, variant, expected, handle)
+            })
+            .collect();
+
+        for (day, variant, expected, handle) in handles {
+            let prefix = format!("{} day {} ({})", year.year, day, variant);
+            match handle.join() {
+                Ok(Ok(got)) => {
+                    for (answer, got) in expected.iter().zip(got) {
+                        if let Value::Failed(e) = got {
+                            failures.push(format!("{} part {}: {}", prefix, answer.part, e));
+                        } else if answer.answer != got.to_string() {
+                            failures.push(format!(
+                                "{} part {}: expected {}, got {}",
+                                prefix, answer.part, answer.answer, got
+                            ));
+                        }
+                    }
+                }
+                Ok(Err(e)) => failures.push(format!("{}: {}", prefix, e)),
+                Err(_) => failures.push(format!("{}: panicked", prefix)),
+            }
+        }
+    });
+    failures
+}
+
+/// Panics with every failure of [`check`], for use from `#[test]`s.
+pub fn assert_recorded(year: &Year) {
+    let failures = check(year);
+    if !failures.is_empty() {
+        panic!(
+            "{} recorded answer(s) failed:\n{}",
+            failures.len(),
+            failures.join("\n")
+        );
+    }
+}
+
+/// The input variants of `day` present in `dir`: the puzzle input if there is
+/// one, followed by consecutively numbered samples.
+pub fn variants(dir: &Path, day: u8) -> Vec<Variant> {
+    let exists = |v: Variant| input::locate(dir, day, v).is_ok();
+    let puzzle = Some(Variant::Puzzle).filter(|&v| exists(v));
+    let samples = (1..).map(Variant::Sample).take_while(|&v| exists(v));
+    puzzle.into_iter().chain(samples).collect()
+}
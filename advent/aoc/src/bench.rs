@@ -0,0 +1,212 @@
+//! Timing of the individual stages of a solution.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::hint::black_box;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::parse::ParseResult;
+use crate::{Day, Part, Solution};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    Parse,
+    Part(Part),
+}
+
+impl Stage {
+    pub const ALL: [Stage; 3] = [Stage::Parse, Stage::Part(Part::One), Stage::Part(Part::Two)];
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+impl FromStr for Stage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Stage::Parse),
+            "part1" => Ok(Stage::Part(Part::One)),
+            "part2" => Ok(Stage::Part(Part::Two)),
+            s => Err(format!("invalid stage {:?}", s)),
+        }
+    }
+}
+
+/// Times one run of each stage of `S` on `input`, in [`Stage::ALL`] order.
+pub(crate) fn time<S: Solution>(input: &str) -> ParseResult<[Duration; 3]> {
+    let start = Instant::now();
+    let data = black_box(S::parse(black_box(input))?);
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    black_box(S::part1(&data));
+    let part1 = start.elapsed();
+
+    let start = Instant::now();
+    black_box(S::part2(&data));
+    let part2 = start.elapsed();
+
+    Ok([parse, part1, part2])
+}
+
+/// Times every stage of `day` over `iterations` runs, after one warm-up run.
+pub fn measure(day: &Day, input: &str, iterations: usize) -> ParseResult<[Stats; 3]> {
+    day.time(input)?;
+    let runs = (0..iterations.max(1))
+        .map(|_| day.time(input))
+        .collect::<ParseResult<Vec<_>>>()?;
+    Ok([0, 1, 2].map(|i| Stats::new(&runs.iter().map(|run| run[i]).collect::<Vec<_>>())))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub min: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty());
+        let secs: Vec<f64> = samples.iter().map(Duration::as_secs_f64).collect();
+        let mean = secs.iter().sum::<f64>() / secs.len() as f64;
+        let var = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / secs.len() as f64;
+        Stats {
+            mean: Duration::from_secs_f64(mean),
+            min: *samples.iter().min().unwrap(),
+            stddev: Duration::from_secs_f64(var.sqrt()),
+        }
+    }
+}
+
+/// Stats of previous runs, keyed by year, day and stage.
+///
+/// Saved as text, one stage per line with the durations in nanoseconds:
+///
+/// ```text
+/// # year day stage mean min stddev
+/// 2021 19 part1 14201337 13990211 120433
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Baseline(pub BTreeMap<(u16, u8, Stage), Stats>);
+
+impl Baseline {
+    pub fn parse(text: &str) -> Result<Baseline, String> {
+        let mut baseline = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let err = |msg: String| format!("line {}: {}", i + 1, msg);
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [year, day, stage, mean, min, stddev] = fields[..] else {
+                return Err(err(format!("expected 6 fields, got {:?}", line)));
+            };
+            let nanos = |s: &str| {
+                s.parse()
+                    .map(Duration::from_nanos)
+                    .map_err(|_| err(format!("invalid duration {:?}", s)))
+            };
+            let key = (
+                year.parse()
+                    .map_err(|_| err(format!("invalid year {:?}", year)))?,
+                day.parse()
+                    .map_err(|_| err(format!("invalid day {:?}", day)))?,
+                stage.parse().map_err(err)?,
+            );
+            let stats = Stats {
+                mean: nanos(mean)?,
+                min: nanos(min)?,
+                stddev: nanos(stddev)?,
+            };
+            baseline.insert(key, stats);
+        }
+        Ok(Baseline(baseline))
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# year day stage mean min stddev")?;
+        for ((year, day, stage), stats) in &self.0 {
+            writeln!(
+                f,
+                "{} {} {} {} {} {}",
+                year,
+                day,
+                stage,
+                stats.mean.as_nanos(),
+                stats.min.as_nanos(),
+                stats.stddev.as_nanos()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(mean: u64, min: u64, stddev: u64) -> Stats {
+        Stats {
+            mean: Duration::from_nanos(mean),
+            min: Duration::from_nanos(min),
+            stddev: Duration::from_nanos(stddev),
+        }
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.0.insert(
+            (2021, 19, Stage::Part(Part::One)),
+            stats(14201337, 13990211, 120433),
+        );
+        baseline.0.insert((2021, 19, Stage::Parse), stats(0, 0, 0));
+        baseline
+            .0
+            .insert((2023, 5, Stage::Part(Part::Two)), stats(1, 1, 0));
+        let text = baseline.to_string();
+        assert_eq!(
+            text,
+            "# year day stage mean min stddev\n\
+             2021 19 parse 0 0 0\n\
+             2021 19 part1 14201337 13990211 120433\n\
+             2023 5 part2 1 1 0\n"
+        );
+        assert_eq!(Baseline::parse(&text).unwrap().0, baseline.0);
+
+        // blank lines, comments and extra spaces are fine
+        let text = "\n# old runs\n  2023  5 part2 1 1 0  \n\n";
+        let parsed = Baseline::parse(text).unwrap();
+        assert_eq!(parsed.0.len(), 1);
+        assert_eq!(parsed.0[&(2023, 5, Stage::Part(Part::Two))], stats(1, 1, 0));
+    }
+
+    #[test]
+    fn baseline_rejects_malformed_lines() {
+        let err = |text| Baseline::parse(text).unwrap_err();
+        assert_eq!(
+            err("# header\n2023 5 part2 1 1"),
+            "line 2: expected 6 fields, got \"2023 5 part2 1 1\""
+        );
+        assert_eq!(err("2023 5 part3 1 1 0"), "line 1: invalid stage \"part3\"");
+        assert_eq!(
+            err("2023 5 parse 1 -1 0"),
+            "line 1: invalid duration \"-1\""
+        );
+        assert_eq!(err("23x 5 parse 1 1 0"), "line 1: invalid year \"23x\"");
+        assert_eq!(err("2023 300 parse 1 1 0"), "line 1: invalid day \"300\"");
+    }
+}
@@ -0,0 +1,521 @@
+//! Arbitrary-precision integers, just enough for exact [`Rational`]s whose
+//! numerators and denominators outgrow an `i128` halfway through a
+//! computation even when the result is small.
+//!
+//! [`Rational`]: crate::rational::Rational
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// Sign and magnitude, the magnitude in base 2^32 limbs with the least
+/// significant first and no zero limbs at the end. Zero has no limbs and is
+/// never negative.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    neg: bool,
+    mag: Vec<u32>,
+}
+
+impl BigInt {
+    pub const ZERO: BigInt = BigInt {
+        neg: false,
+        mag: Vec::new(),
+    };
+
+    fn from_parts(neg: bool, mut mag: Vec<u32>) -> BigInt {
+        trim(&mut mag);
+        BigInt {
+            neg: neg && !mag.is_empty(),
+            mag,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mag.is_empty()
+    }
+
+    pub fn is_one(&self) -> bool {
+        !self.neg && self.mag == [1]
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.neg
+    }
+
+    pub fn abs(&self) -> BigInt {
+        BigInt::from_parts(false, self.mag.clone())
+    }
+
+    /// The number of bits of the magnitude.
+    pub fn bits(&self) -> u64 {
+        match self.mag.last() {
+            Some(top) => self.mag.len() as u64 * 32 - top.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    /// Division rounding towards zero and the matching remainder, which has
+    /// the sign of `self`, like for primitive integers.
+    pub fn div_rem(&self, rhs: &BigInt) -> (BigInt, BigInt) {
+        assert!(!rhs.is_zero(), "attempt to divide by zero");
+        let (q, r) = div_rem_mag(&self.mag, &rhs.mag);
+        (
+            BigInt::from_parts(self.neg != rhs.neg, q),
+            BigInt::from_parts(self.neg, r),
+        )
+    }
+
+    /// The non-negative greatest common divisor, `gcd(0, 0) == 0`.
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        BigInt::from_parts(false, gcd_mag(&self.mag, &other.mag))
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.mag.len() > 4 {
+            return None;
+        }
+        let mag = self
+            .mag
+            .iter()
+            .rev()
+            .fold(0u128, |acc, &limb| acc << 32 | limb as u128);
+        if self.neg {
+            0i128.checked_sub_unsigned(mag)
+        } else {
+            i128::try_from(mag).ok()
+        }
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        self.to_i128().and_then(|n| i64::try_from(n).ok())
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(n: i128) -> BigInt {
+        let mut mag = n.unsigned_abs();
+        let mut limbs = vec![];
+        while mag > 0 {
+            limbs.push(mag as u32);
+            mag >>= 32;
+        }
+        BigInt::from_parts(n < 0, limbs)
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(n: i64) -> BigInt {
+        BigInt::from(n as i128)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.neg, other.neg) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_mag(&self.mag, &other.mag),
+            (true, true) => cmp_mag(&other.mag, &self.mag),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        // peel off 9 decimal digits at a time
+        let mut chunks = vec![];
+        let mut mag = self.mag.clone();
+        while !mag.is_empty() {
+            let (q, r) = div_rem_small(&mag, 1_000_000_000);
+            chunks.push(r);
+            mag = q;
+        }
+        if self.neg {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.neg, self.mag.clone())
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, rhs: &BigInt) -> BigInt {
+        if self.neg == rhs.neg {
+            return BigInt::from_parts(self.neg, add_mag(&self.mag, &rhs.mag));
+        }
+        match cmp_mag(&self.mag, &rhs.mag) {
+            Ordering::Less => BigInt::from_parts(rhs.neg, sub_mag(&rhs.mag, &self.mag)),
+            _ => BigInt::from_parts(self.neg, sub_mag(&self.mag, &rhs.mag)),
+        }
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: &BigInt) -> BigInt {
+        self + &-rhs
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: &BigInt) -> BigInt {
+        BigInt::from_parts(self.neg != rhs.neg, mul_mag(&self.mag, &rhs.mag))
+    }
+}
+
+impl Div for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, rhs: &BigInt) -> BigInt {
+        self.div_rem(rhs).0
+    }
+}
+
+impl Rem for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, rhs: &BigInt) -> BigInt {
+        self.div_rem(rhs).1
+    }
+}
+
+// The owned versions just borrow.
+macro_rules! forward_binop {
+    ($($trait:ident $method:ident),*) => {$(
+        impl $trait for BigInt {
+            type Output = BigInt;
+
+            fn $method(self, rhs: BigInt) -> BigInt {
+                (&self).$method(&rhs)
+            }
+        }
+    )*};
+}
+
+forward_binop!(Add add, Sub sub, Mul mul, Div div, Rem rem);
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.neg, self.mag)
+    }
+}
+
+fn trim(mag: &mut Vec<u32>) {
+    while mag.last() == Some(&0) {
+        mag.pop();
+    }
+}
+
+fn cmp_mag(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut out = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0;
+    for (i, &x) in a.iter().enumerate() {
+        let sum = x as u64 + b.get(i).copied().unwrap_or(0) as u64 + carry;
+        out.push(sum as u32);
+        carry = sum >> 32;
+    }
+    if carry > 0 {
+        out.push(carry as u32);
+    }
+    out
+}
+
+// a - b for a >= b
+fn sub_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut out = a.to_vec();
+    sub_assign_mag(&mut out, b);
+    out
+}
+
+fn sub_assign_mag(a: &mut Vec<u32>, b: &[u32]) {
+    let mut borrow = false;
+    for (i, x) in a.iter_mut().enumerate() {
+        let (d, b1) = x.overflowing_sub(b.get(i).copied().unwrap_or(0));
+        let (d, b2) = d.overflowing_sub(borrow as u32);
+        *x = d;
+        borrow = b1 || b2;
+    }
+    debug_assert!(!borrow, "subtracting a larger magnitude");
+    trim(a);
+}
+
+fn mul_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut out = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &y) in b.iter().enumerate() {
+            let t = out[i + j] as u64 + x as u64 * y as u64 + carry;
+            out[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        out[i + b.len()] = carry as u32;
+    }
+    trim(&mut out);
+    out
+}
+
+fn div_rem_small(a: &[u32], d: u32) -> (Vec<u32>, u32) {
+    let mut q = vec![0u32; a.len()];
+    let mut rem = 0u64;
+    for i in (0..a.len()).rev() {
+        let cur = rem << 32 | a[i] as u64;
+        q[i] = (cur / d as u64) as u32;
+        rem = cur % d as u64;
+    }
+    trim(&mut q);
+    (q, rem as u32)
+}
+
+// Schoolbook long division one bit at a time, which is plenty for numbers of
+// a few hundred bits.
+fn div_rem_mag(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if cmp_mag(a, b) == Ordering::Less {
+        return (vec![], a.to_vec());
+    }
+    if let [d] = b {
+        let (q, r) = div_rem_small(a, *d);
+        return (q, if r == 0 { vec![] } else { vec![r] });
+    }
+    let mut q = vec![0u32; a.len()];
+    let mut r: Vec<u32> = vec![];
+    for bit in (0..a.len() * 32).rev() {
+        shl_mag(&mut r, 1);
+        if a[bit / 32] >> (bit % 32) & 1 == 1 {
+            if r.is_empty() {
+                r.push(0);
+            }
+            r[0] |= 1;
+        }
+        if cmp_mag(&r, b) != Ordering::Less {
+            sub_assign_mag(&mut r, b);
+            q[bit / 32] |= 1 << (bit % 32);
+        }
+    }
+    trim(&mut q);
+    (q, r)
+}
+
+fn shl_mag(a: &mut Vec<u32>, n: u32) {
+    if a.is_empty() {
+        return;
+    }
+    let (limbs, bits) = ((n / 32) as usize, n % 32);
+    if bits > 0 {
+        let mut carry = 0;
+        for x in a.iter_mut() {
+            let next = *x >> (32 - bits);
+            *x = *x << bits | carry;
+            carry = next;
+        }
+        if carry > 0 {
+            a.push(carry);
+        }
+    }
+    a.splice(0..0, std::iter::repeat_n(0, limbs));
+}
+
+fn shr_mag(a: &mut Vec<u32>, n: u32) {
+    let (limbs, bits) = ((n / 32) as usize, n % 32);
+    a.drain(..limbs.min(a.len()));
+    if bits > 0 {
+        for i in 0..a.len() {
+            let high = a.get(i + 1).map_or(0, |&x| x << (32 - bits));
+            a[i] = a[i] >> bits | high;
+        }
+    }
+    trim(a);
+}
+
+fn trailing_zeros(a: &[u32]) -> u32 {
+    let i = a.iter().position(|&x| x != 0).unwrap();
+    i as u32 * 32 + a[i].trailing_zeros()
+}
+
+// Binary gcd, which only needs shifts and subtractions.
+fn gcd_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() {
+        return b.to_vec();
+    }
+    if b.is_empty() {
+        return a.to_vec();
+    }
+    let (mut a, mut b) = (a.to_vec(), b.to_vec());
+    let (za, zb) = (trailing_zeros(&a), trailing_zeros(&b));
+    shr_mag(&mut a, za);
+    shr_mag(&mut b, zb);
+    // both odd from here on
+    loop {
+        match cmp_mag(&a, &b) {
+            Ordering::Equal => break,
+            Ordering::Greater => {
+                sub_assign_mag(&mut a, &b);
+                let z = trailing_zeros(&a);
+                shr_mag(&mut a, z);
+            }
+            Ordering::Less => {
+                sub_assign_mag(&mut b, &a);
+                let z = trailing_zeros(&b);
+                shr_mag(&mut b, z);
+            }
+        }
+    }
+    shl_mag(&mut a, za.min(zb));
+    a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(n: i128) -> BigInt {
+        BigInt::from(n)
+    }
+
+    fn gcd_i128(a: i128, b: i128) -> i128 {
+        let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a as i128
+    }
+
+    // Numbers of every size from one to four limbs, both signs, plus the
+    // awkward ones at the limb boundaries.
+    fn samples() -> Vec<i128> {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        let mut nums = vec![
+            0,
+            1,
+            -1,
+            7,
+            -7,
+            1 << 32,
+            (1 << 32) - 1,
+            -(1 << 64),
+            i128::MAX,
+        ];
+        nums.push(i128::MIN + 1);
+        for bits in [5, 31, 33, 63, 64, 65, 95, 97, 126] {
+            for _ in 0..4 {
+                let n = ((next() as u128) << 64 | next() as u128) >> (128 - bits);
+                nums.push(n as i128);
+                nums.push(-(n as i128));
+            }
+        }
+        nums
+    }
+
+    #[test]
+    fn div_rem_matches_i128() {
+        let nums = samples();
+        for &a in &nums {
+            for &b in &nums {
+                if b == 0 {
+                    continue;
+                }
+                let (q, r) = big(a).div_rem(&big(b));
+                assert_eq!(q.to_i128(), Some(a / b), "{} / {}", a, b);
+                assert_eq!(r.to_i128(), Some(a % b), "{} % {}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn gcd_matches_i128() {
+        let nums = samples();
+        for &a in &nums {
+            for &b in &nums {
+                assert_eq!(
+                    big(a).gcd(&big(b)).to_i128(),
+                    Some(gcd_i128(a, b)),
+                    "{} {}",
+                    a,
+                    b
+                );
+            }
+        }
+        // a common factor well past i128
+        let f = big(i128::MAX) * big(1 << 100);
+        assert_eq!((&f * &big(12)).gcd(&(&f * &big(-18))), &f * &big(6));
+    }
+
+    #[test]
+    fn display_matches_i128() {
+        for n in samples() {
+            assert_eq!(big(n).to_string(), n.to_string());
+        }
+        assert_eq!(big(-1_000_000_000).to_string(), "-1000000000");
+        assert_eq!(big(1_000_000_007).to_string(), "1000000007");
+        let huge = big(i128::MAX) * big(i128::MAX);
+        assert_eq!(
+            huge.to_string(),
+            "28948022309329048855892746252171976962977213799489202546401021394546514198529"
+        );
+    }
+
+    #[test]
+    fn arithmetic_past_i128() {
+        let nums = samples();
+        for &a in &nums {
+            for &b in &nums {
+                let (x, y) = (big(a), big(b));
+                assert_eq!((&x + &y) - y.clone(), x);
+                if b != 0 {
+                    // exact division undoes the multiplication
+                    assert_eq!(&(&x * &y) / &y, x, "{} * {}", a, b);
+                }
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+            }
+        }
+        assert_eq!((big(i128::MAX) + big(1)).to_i128(), None);
+        assert_eq!(big(i128::MIN + 1) - big(1), big(i128::MIN));
+        assert_eq!(big(i128::MIN).to_i128(), Some(i128::MIN));
+        assert_eq!(big(-5).abs(), big(5));
+        assert_eq!((big(1) - big(1)), BigInt::ZERO);
+        assert!(!(big(1) - big(1)).is_negative());
+    }
+}
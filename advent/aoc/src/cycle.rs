@@ -0,0 +1,268 @@
+//! Cycle detection for simulations that end up repeating themselves.
+//!
+//! All finders take the initial state `x0` and a step function `f` and
+//! describe the sequence `x0, f(x0), f(f(x0)), ...` with a [`Cycle`]: `start`
+//! states lead into a loop of `period` states. A simulation settling into a
+//! fixed point is a cycle with period 1.
+//!
+//! [`find`] hashes every state it sees and so calls `f` the least and keeps
+//! the states around, [`brent`] and [`floyd`] only need `Eq` and keep a couple
+//! of states at a time, at the cost of stepping more often.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// The index of the first state in the loop.
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The index of the first occurrence of the state at step `n`, which is
+    /// below `start + period`.
+    pub fn index(&self, n: u64) -> usize {
+        let start = self.start as u64;
+        if n < start {
+            n as usize
+        } else {
+            (start + (n - start) % self.period as u64) as usize
+        }
+    }
+
+    /// The state at step `n`, given the states found by [`find`].
+    pub fn nth<'a, S>(&self, states: &'a [S], n: u64) -> &'a S {
+        &states[self.index(n)]
+    }
+
+    /// Extrapolates a metric of the sequence to step `n`, given its value at
+    /// every step up to and including `start + period`. The metric may grow
+    /// each time round the loop, like a running total: it's assumed to grow by
+    /// the same amount every time.
+    pub fn extrapolate(&self, values: &[i64], n: u64) -> i64 {
+        let end = self.start + self.period;
+        assert!(
+            values.len() > end,
+            "need the first {} values, got {}",
+            end + 1,
+            values.len()
+        );
+        let i = self.index(n);
+        let laps = if n < self.start as u64 {
+            0
+        } else {
+            ((n - self.start as u64) / self.period as u64) as i64
+        };
+        values[i] + laps * (values[end] - values[self.start])
+    }
+}
+
+/// Steps until the state stops changing, the special case of a cycle with
+/// period 1, which only needs to compare each state with the one before.
+/// Returns the first stable state and its index, i.e. the cycle's `start`.
+pub fn settle<S: Eq>(x0: S, mut f: impl FnMut(&S) -> S) -> (usize, S) {
+    let mut x = x0;
+    for i in 0.. {
+        let next = f(&x);
+        if next == x {
+            return (i, x);
+        }
+        x = next;
+    }
+    unreachable!();
+}
+
+/// Finds the cycle by remembering every state. Also returns the states in
+/// order up to the end of the first loop, i.e. `start + period` of them.
+pub fn find<S: Clone + Eq + Hash>(x0: S, mut f: impl FnMut(&S) -> S) -> (Cycle, Vec<S>) {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut x = x0;
+    loop {
+        if let Some(&start) = seen.get(&x) {
+            let cycle = Cycle {
+                start,
+                period: states.len() - start,
+            };
+            return (cycle, states);
+        }
+        let next = f(&x);
+        seen.insert(x.clone(), states.len());
+        states.push(x);
+        x = next;
+    }
+}
+
+/// Brent's algorithm: finds the period by stepping a hare ahead of a
+/// tortoise which teleports to it at every power of two, then the start.
+pub fn brent<S: Clone + Eq>(x0: S, mut f: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = f(&hare);
+        period += 1;
+    }
+
+    let mut tortoise = x0.clone();
+    let mut hare = x0;
+    for _ in 0..period {
+        hare = f(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+    Cycle { start, period }
+}
+
+/// Floyd's algorithm: a hare running twice as fast as the tortoise meets it
+/// somewhere in the loop.
+pub fn floyd<S: Clone + Eq>(x0: S, mut f: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = f(&x0);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let next = f(&hare);
+        hare = f(&next);
+    }
+
+    let mut start = 0;
+    let mut tortoise = x0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut period = 1;
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        period += 1;
+    }
+    Cycle { start, period }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, ..., 6 and then 7, 8, 9, 10, 11 over and over
+    fn rho(&x: &u64) -> u64 {
+        if x < 7 {
+            x + 1
+        } else {
+            7 + (x - 6) % 5
+        }
+    }
+
+    fn all_finders(x0: u64, f: fn(&u64) -> u64) -> Cycle {
+        let (cycle, states) = find(x0, f);
+        assert_eq!(states.len(), cycle.start + cycle.period);
+        assert_eq!(brent(x0, f), cycle, "brent from {}", x0);
+        assert_eq!(floyd(x0, f), cycle, "floyd from {}", x0);
+        cycle
+    }
+
+    #[test]
+    fn finders_agree() {
+        assert_eq!(
+            all_finders(0, rho),
+            Cycle {
+                start: 7,
+                period: 5
+            }
+        );
+        // starting inside the loop
+        assert_eq!(
+            all_finders(9, rho),
+            Cycle {
+                start: 0,
+                period: 5
+            }
+        );
+        // a fixed point
+        assert_eq!(
+            all_finders(4, |&x| x.max(5)),
+            Cycle {
+                start: 1,
+                period: 1
+            }
+        );
+        assert_eq!(
+            all_finders(5, |&x| x),
+            Cycle {
+                start: 0,
+                period: 1
+            }
+        );
+        // a pseudorandom walk, long tail and loop
+        let walk = |&x: &u64| (x * x + 1) % 1_000_003;
+        assert_eq!(
+            all_finders(3, walk),
+            Cycle {
+                start: 1454,
+                period: 116
+            }
+        );
+    }
+
+    #[test]
+    fn nth_state() {
+        let (cycle, states) = find(0, rho);
+        let mut x = 0;
+        for n in 0..40 {
+            assert_eq!(*cycle.nth(&states, n), x, "step {}", n);
+            x = rho(&x);
+        }
+        assert_eq!(
+            cycle.index(1_000_000_000_000),
+            7 + (1_000_000_000_000 - 7) % 5
+        );
+    }
+
+    #[test]
+    fn extrapolate_running_total() {
+        let (cycle, states) = find(0, rho);
+        let mut totals = vec![];
+        let mut total = 0;
+        for x in states.iter().copied().chain([rho(states.last().unwrap())]) {
+            total += x as i64;
+            totals.push(total);
+        }
+        // exact within the values given
+        for n in 0..totals.len() {
+            assert_eq!(cycle.extrapolate(&totals, n as u64), totals[n]);
+        }
+        // checked by stepping, a hundred steps out
+        let mut x = 0;
+        let mut total = 0;
+        for n in 0..100 {
+            total += x as i64;
+            assert_eq!(cycle.extrapolate(&totals, n), total, "step {}", n);
+            x = rho(&x);
+        }
+        // 0 to 6, then 45 for every whole lap of 7 to 11 and 7 + 8 + 9 for
+        // the three steps left
+        assert_eq!(
+            cycle.extrapolate(&totals, 1_000_000_000_000),
+            8_999_999_999_965
+        );
+    }
+
+    #[test]
+    fn settle_stops_at_a_fixed_point() {
+        assert_eq!(settle(37u32, |&x| x / 2), (6, 0));
+        assert_eq!(settle(0u32, |&x| x), (0, 0));
+    }
+}
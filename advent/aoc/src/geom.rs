@@ -0,0 +1,307 @@
+//! Directions on a grid and points on the unbounded plane.
+//!
+//! Both follow the screen convention of the input text: `y` (the row) grows
+//! downwards and `x` (the column) to the right, so [`Dir::U`] decreases `y`.
+//! Stepping a bounded [`Pos`] goes through [`Grid::step`].
+//!
+//! [`Grid::step`]: crate::Grid::step
+
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::grid::Pos;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir {
+    U,
+    D,
+    L,
+    R,
+}
+
+impl Dir {
+    /// Clockwise starting from the right, so that the index of a direction is
+    /// also its digit in the `0-3` notation.
+    pub const ALL: [Dir; 4] = [Dir::R, Dir::D, Dir::L, Dir::U];
+
+    fn index(self) -> usize {
+        match self {
+            Dir::R => 0,
+            Dir::D => 1,
+            Dir::L => 2,
+            Dir::U => 3,
+        }
+    }
+
+    /// The direction after turning a quarter counterclockwise.
+    pub fn turn_left(self) -> Dir {
+        Dir::ALL[(self.index() + 3) % 4]
+    }
+
+    /// The direction after turning a quarter clockwise.
+    pub fn turn_right(self) -> Dir {
+        Dir::ALL[(self.index() + 1) % 4]
+    }
+
+    pub fn reverse(self) -> Dir {
+        Dir::ALL[(self.index() + 2) % 4]
+    }
+
+    /// Both directions at right angles to this one.
+    pub fn perpendicular(self) -> [Dir; 2] {
+        [self.turn_left(), self.turn_right()]
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Dir::U | Dir::D)
+    }
+
+    /// A distinct bit per direction, to keep a set of them in a `u8`.
+    pub fn mask(self) -> u8 {
+        1 << self.index()
+    }
+
+    /// The `(row, col)` offset of a single step.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Dir::U => (-1, 0),
+            Dir::D => (1, 0),
+            Dir::L => (0, -1),
+            Dir::R => (0, 1),
+        }
+    }
+
+    /// Parses `U`, `D`, `L` or `R`.
+    pub fn from_letter(c: char) -> Option<Dir> {
+        match c {
+            'U' => Some(Dir::U),
+            'D' => Some(Dir::D),
+            'L' => Some(Dir::L),
+            'R' => Some(Dir::R),
+            _ => None,
+        }
+    }
+
+    /// Parses `^`, `v`, `<` or `>`.
+    pub fn from_arrow(c: char) -> Option<Dir> {
+        match c {
+            '^' => Some(Dir::U),
+            'v' => Some(Dir::D),
+            '<' => Some(Dir::L),
+            '>' => Some(Dir::R),
+            _ => None,
+        }
+    }
+
+    /// Parses `0` to `3`, meaning right, down, left and up.
+    pub fn from_digit(c: char) -> Option<Dir> {
+        c.to_digit(4).map(|d| Dir::ALL[d as usize])
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Dir::U => '^',
+            Dir::D => 'v',
+            Dir::L => '<',
+            Dir::R => '>',
+        }
+    }
+}
+
+/// Accepts both letters and arrows, see [`Dir::from_letter`] and
+/// [`Dir::from_arrow`].
+impl TryFrom<char> for Dir {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Dir::from_letter(c)
+            .or_else(|| Dir::from_arrow(c))
+            .ok_or("U, D, L, R or an arrow")
+    }
+}
+
+impl FromStr for Dir {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Dir::try_from(c),
+            _ => Err("U, D, L, R or an arrow"),
+        }
+    }
+}
+
+impl Display for Dir {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match self {
+            Dir::U => 'U',
+            Dir::D => 'D',
+            Dir::L => 'L',
+            Dir::R => 'R',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    /// The point one step away in `dir`.
+    pub fn step(self, dir: Dir) -> Point {
+        self.moved(dir, 1)
+    }
+
+    /// The point `n` steps away in `dir`.
+    pub fn moved(self, dir: Dir, n: i64) -> Point {
+        self + Point::from(dir) * n
+    }
+
+    /// Like [`Point::moved`], or `None` on overflow.
+    pub fn checked_moved(self, dir: Dir, n: i64) -> Option<Point> {
+        let (dy, dx) = dir.delta();
+        Some(Point {
+            x: self.x.checked_add((dx as i64).checked_mul(n)?)?,
+            y: self.y.checked_add((dy as i64).checked_mul(n)?)?,
+        })
+    }
+
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The 2D cross product, twice the signed area of the triangle spanned by
+    /// the origin and both points.
+    pub fn cross(self, other: Point) -> i64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// The grid position of the point, if it has no negative coordinate.
+    pub fn to_pos(self) -> Option<Pos> {
+        Some((usize::try_from(self.y).ok()?, usize::try_from(self.x).ok()?))
+    }
+}
+
+impl From<Dir> for Point {
+    fn from(dir: Dir) -> Point {
+        let (dy, dx) = dir.delta();
+        Point::new(dx as i64, dy as i64)
+    }
+}
+
+impl From<Pos> for Point {
+    fn from((row, col): Pos) -> Point {
+        Point::new(col as i64, row as i64)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i64) -> Point {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_every_notation() {
+        let letters = "UDLR".chars().map(Dir::from_letter).collect::<Vec<_>>();
+        let arrows = "^v<>".chars().map(Dir::from_arrow).collect::<Vec<_>>();
+        let all = [Dir::U, Dir::D, Dir::L, Dir::R].map(Some);
+        assert_eq!(letters, all);
+        assert_eq!(arrows, all);
+        // digits go clockwise from the right, as in 2023 day 18
+        let digits = "0123".chars().map(Dir::from_digit).collect::<Vec<_>>();
+        assert_eq!(digits, [Dir::R, Dir::D, Dir::L, Dir::U].map(Some));
+
+        assert_eq!(Dir::from_letter('u'), None);
+        assert_eq!(Dir::from_letter('^'), None);
+        assert_eq!(Dir::from_arrow('V'), None);
+        assert_eq!(Dir::from_digit('4'), None);
+        assert_eq!(Dir::from_digit('a'), None);
+    }
+
+    #[test]
+    fn from_str_takes_a_single_letter_or_arrow() {
+        assert_eq!("L".parse::<Dir>(), Ok(Dir::L));
+        assert_eq!(">".parse::<Dir>(), Ok(Dir::R));
+        assert!("".parse::<Dir>().is_err());
+        assert!("UD".parse::<Dir>().is_err());
+        assert!("2".parse::<Dir>().is_err());
+        for dir in Dir::ALL {
+            assert_eq!(dir.to_string().parse(), Ok(dir));
+            assert_eq!(Dir::try_from(dir.arrow()), Ok(dir));
+        }
+    }
+
+    #[test]
+    fn turning_and_stepping() {
+        for dir in Dir::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            assert_eq!(Point::ORIGIN.step(dir).step(dir.reverse()), Point::ORIGIN);
+            assert!(dir
+                .perpendicular()
+                .iter()
+                .all(|d| d.is_vertical() != dir.is_vertical()));
+        }
+        assert_eq!(Dir::U.turn_right(), Dir::R);
+        let masks = Dir::ALL.iter().fold(0, |m, d| m | d.mask());
+        assert_eq!(masks, 0b1111);
+        assert_eq!(Point::ORIGIN.moved(Dir::U, 3), Point::new(0, -3));
+        assert_eq!(Point::new(i64::MAX, 0).checked_moved(Dir::R, 1), None);
+        assert_eq!(Point::new(2, 1).to_pos(), Some((1, 2)));
+        assert_eq!(Point::new(-1, 1).to_pos(), None);
+    }
+}
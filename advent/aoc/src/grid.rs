@@ -0,0 +1,451 @@
+//! A rectangular grid of cells, the shape most puzzle inputs come in.
+//!
+//! Cells are stored flat in row-major order and addressed by `(row, col)`,
+//! with `(0, 0)` the top left corner, i.e. the first char of the input.
+
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+
+use crate::geom::Dir;
+use crate::parse::{self, ParseError, ParseResult};
+
+/// A `(row, col)` position in a [`Grid`].
+pub type Pos = (usize, usize);
+
+/// Offsets of the four orthogonal neighbours: up, down, left and right.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+/// Offsets of the four diagonal neighbours.
+pub const DIAGONAL: [(isize, isize); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
+/// Offsets of all eight neighbours, orthogonal ones first.
+pub const KING: [(isize, isize); 8] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (-1, 1),
+    (1, -1),
+    (1, 1),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A `rows` x `cols` grid with every cell set to `value`.
+    pub fn new(rows: usize, cols: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            rows,
+            cols,
+            cells: vec![value; rows * cols],
+        }
+    }
+
+    /// A `rows` x `cols` grid with each cell computed from its position.
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(Pos) -> T) -> Grid<T> {
+        let cells = (0..rows)
+            .flat_map(|r| (0..cols).map(move |c| (r, c)))
+            .map(&mut f)
+            .collect();
+        Grid { rows, cols, cells }
+    }
+
+    /// Builds a grid out of `rows`, or `None` if they differ in length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let cols = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|r| r.len() != cols) {
+            return None;
+        }
+        Some(Grid {
+            rows: rows.len(),
+            cols,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses one line of `input` per row, mapping every char with `f` like
+    /// [`parse::chars`] does, e.g. `Grid::parse(input, Tile::try_from)`.
+    /// All rows must be as long as the first one.
+    pub fn parse<E: Display>(
+        input: &str,
+        f: impl FnMut(char) -> Result<T, E>,
+    ) -> ParseResult<Grid<T>> {
+        if input.lines().next().is_none() {
+            return Err(ParseError::after(input, "a grid"));
+        }
+        Grid::parse_lines(input.lines(), f)
+    }
+
+    /// Like [`Grid::parse`] for input already split into lines, e.g. a
+    /// section from [`parse::sections`].
+    pub fn parse_lines<'a, E: Display>(
+        lines: impl IntoIterator<Item = &'a str>,
+        mut f: impl FnMut(char) -> Result<T, E>,
+    ) -> ParseResult<Grid<T>> {
+        let mut rows = 0;
+        let mut cols = None;
+        let mut cells = Vec::new();
+        let mut last = "";
+        for line in lines {
+            let row = parse::chars(line, &mut f)?;
+            match cols {
+                None => cols = Some(row.len()),
+                Some(cols) if cols != row.len() => {
+                    return Err(ParseError::new(line, format!("a row of {} cells", cols)));
+                }
+                Some(_) => {}
+            }
+            cells.extend(row);
+            rows += 1;
+            last = line;
+        }
+        match cols {
+            Some(cols) if cols > 0 => Ok(Grid { rows, cols, cells }),
+            _ => Err(ParseError::after(last, "a grid")),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, (r, c): Pos) -> bool {
+        r < self.rows && c < self.cols
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        self.contains(pos).then(|| pos.0 * self.cols + pos.1)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// `pos` moved by `(dr, dc)`, if that's still inside the grid.
+    pub fn shift(&self, (r, c): Pos, (dr, dc): (isize, isize)) -> Option<Pos> {
+        let pos = (r.checked_add_signed(dr)?, c.checked_add_signed(dc)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// `pos` moved a step in `dir`, if that's still inside the grid.
+    pub fn step(&self, pos: Pos, dir: Dir) -> Option<Pos> {
+        self.shift(pos, dir.delta())
+    }
+
+    /// `pos` moved by `(dr, dc)`, wrapping around the edges.
+    pub fn wrapping_shift(&self, (r, c): Pos, (dr, dc): (isize, isize)) -> Pos {
+        let wrap = |x: usize, d: isize, n: usize| (x as isize + d).rem_euclid(n as isize) as usize;
+        (wrap(r, dr, self.rows), wrap(c, dc, self.cols))
+    }
+
+    /// The positions `offsets` away from `pos` that are inside the grid, e.g.
+    /// `grid.neighbours(pos, DIAGONAL)`. The iterator doesn't borrow the grid,
+    /// so cells can be updated while going through it.
+    pub fn neighbours(
+        &self,
+        pos: Pos,
+        offsets: impl IntoIterator<Item = (isize, isize)>,
+    ) -> impl Iterator<Item = Pos> {
+        let (rows, cols) = (self.rows, self.cols);
+        offsets.into_iter().filter_map(move |(dr, dc)| {
+            let pos = (pos.0.checked_add_signed(dr)?, pos.1.checked_add_signed(dc)?);
+            (pos.0 < rows && pos.1 < cols).then_some(pos)
+        })
+    }
+
+    /// The orthogonal neighbours of `pos` inside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        self.neighbours(pos, ORTHOGONAL)
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` inside the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        self.neighbours(pos, KING)
+    }
+
+    /// The positions `offsets` away from `pos` on a grid whose opposite edges
+    /// are joined together.
+    pub fn wrapping_neighbours(
+        &self,
+        pos: Pos,
+        offsets: impl IntoIterator<Item = (isize, isize)>,
+    ) -> impl Iterator<Item = Pos> {
+        let (rows, cols) = (self.rows as isize, self.cols as isize);
+        offsets.into_iter().map(move |(dr, dc)| {
+            let r = (pos.0 as isize + dr).rem_euclid(rows);
+            let c = (pos.1 as isize + dc).rem_euclid(cols);
+            (r as usize, c as usize)
+        })
+    }
+
+    /// The positions `offsets` away from `pos` on the infinite plane tiled with
+    /// copies of the grid, each along with the position it corresponds to in
+    /// the grid itself.
+    pub fn tiled_neighbours(
+        &self,
+        pos: (i64, i64),
+        offsets: impl IntoIterator<Item = (isize, isize)>,
+    ) -> impl Iterator<Item = ((i64, i64), Pos)> {
+        let (rows, cols) = (self.rows as i64, self.cols as i64);
+        offsets.into_iter().map(move |(dr, dc)| {
+            let (r, c) = (pos.0 + dr as i64, pos.1 + dc as i64);
+            (
+                (r, c),
+                (r.rem_euclid(rows) as usize, c.rem_euclid(cols) as usize),
+            )
+        })
+    }
+
+    /// The cell at `pos` on the infinite plane tiled with copies of the grid.
+    pub fn get_tiled(&self, (r, c): (i64, i64)) -> &T {
+        let r = r.rem_euclid(self.rows as i64) as usize;
+        let c = c.rem_euclid(self.cols as i64) as usize;
+        &self[(r, c)]
+    }
+
+    /// Replaces the cell at `pos` and returns the previous value, or gives
+    /// `value` back if `pos` is out of bounds.
+    pub fn set(&mut self, pos: Pos, value: T) -> Result<T, T> {
+        match self.get_mut(pos) {
+            Some(cell) => Ok(std::mem::replace(cell, value)),
+            None => Err(value),
+        }
+    }
+
+    /// The cells of row `r`, left to right.
+    pub fn row(&self, r: usize) -> &[T] {
+        &self.cells[r * self.cols..(r + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, r: usize) -> &mut [T] {
+        &mut self.cells[r * self.cols..(r + 1) * self.cols]
+    }
+
+    /// The cells of column `c`, top to bottom.
+    pub fn col(&self, c: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(c < self.cols, "column {} out of bounds", c);
+        self.cells[c..].iter().step_by(self.cols)
+    }
+
+    /// All rows, top to bottom.
+    pub fn iter_rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.cols.max(1))
+    }
+
+    /// All columns, left to right.
+    pub fn iter_cols(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.cols).map(|c| self.col(c))
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows * cols).map(move |i| (i / cols, i % cols))
+    }
+
+    /// All cells with their positions, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> + '_ {
+        self.positions().zip(&mut self.cells)
+    }
+
+    /// The cells in row-major order.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// The first position, in row-major order, whose cell matches `pred`.
+    pub fn position(&self, mut pred: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, v)| pred(v)).map(|(p, _)| p)
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            cells: self.cells.iter().map(&mut f).collect(),
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.cols, self.rows, |(r, c)| self[(c, r)].clone())
+    }
+
+    /// Rotates the grid a quarter turn clockwise.
+    pub fn rotate_right(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.cols, self.rows, |(r, c)| {
+            self[(self.rows - 1 - c, r)].clone()
+        })
+    }
+
+    /// Rotates the grid a quarter turn counterclockwise.
+    pub fn rotate_left(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.cols, self.rows, |(r, c)| {
+            self[(c, self.cols - 1 - r)].clone()
+        })
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.rows, self.cols, |(r, c)| {
+            self[(r, self.cols - 1 - c)].clone()
+        })
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.rows, self.cols, |(r, c)| {
+            self[(self.rows - 1 - r, c)].clone()
+        })
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.index_of(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!(
+                "position {:?} out of bounds for a {}x{} grid",
+                pos, self.rows, self.cols
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        match self.index_of(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!(
+                "position {:?} out of bounds for a {}x{} grid",
+                pos, self.rows, self.cols
+            ),
+        }
+    }
+}
+
+/// Writes one line per row, without a trailing newline, so a grid of `char`s
+/// prints back as the text it was parsed from.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (r, row) in self.iter_rows().enumerate() {
+            if r > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> Grid<char> {
+        Grid::parse(text, Ok::<_, String>).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        let g = grid("abc\ndef");
+        assert_eq!((g.rows(), g.cols()), (2, 3));
+        assert_eq!(g[(1, 0)], 'd');
+        assert_eq!(g.get((2, 0)), None);
+        assert_eq!(g.to_string(), "abc\ndef");
+        assert!(Grid::parse("ab\nc", Ok::<_, String>).is_err());
+        assert!(Grid::parse("", Ok::<_, String>).is_err());
+    }
+
+    #[test]
+    fn transpose_rotate_and_flip() {
+        let g = grid("abc\ndef");
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(g.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(g.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(g.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(g.flip_vertical().to_string(), "def\nabc");
+
+        assert_eq!(g.transpose().transpose(), g);
+        assert_eq!(g.rotate_right().rotate_left(), g);
+        assert_eq!(
+            g.rotate_right().rotate_right(),
+            g.flip_horizontal().flip_vertical()
+        );
+        let four = (0..4).fold(g.clone(), |g, _| g.rotate_right());
+        assert_eq!(four, g);
+        // a transpose is a flip of a quarter turn
+        assert_eq!(g.transpose(), g.rotate_right().flip_horizontal());
+    }
+
+    #[test]
+    fn tiled_neighbours_wrap_in_both_directions() {
+        let g = grid("ab\ncd\nef");
+        let n = g.tiled_neighbours((0, 0), ORTHOGONAL).collect::<Vec<_>>();
+        assert_eq!(
+            n,
+            [
+                ((-1, 0), (2, 0)),
+                ((1, 0), (1, 0)),
+                ((0, -1), (0, 1)),
+                ((0, 1), (0, 1))
+            ]
+        );
+        // far away copies map back to the same cell
+        let n = g.tiled_neighbours((-7, 5), [(0, 0), (-3, -2)]);
+        assert_eq!(
+            n.collect::<Vec<_>>(),
+            [((-7, 5), (2, 1)), ((-10, 3), (2, 1))]
+        );
+        assert_eq!(*g.get_tiled((-7, 5)), 'f');
+        assert_eq!(*g.get_tiled((300, -300)), 'a');
+    }
+
+    #[test]
+    fn bounded_neighbours_and_set() {
+        let mut g = grid("ab\ncd");
+        assert_eq!(g.neighbours4((0, 0)).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(g.neighbours8((1, 1)).count(), 3);
+        assert_eq!(g.set((1, 1), 'x'), Ok('d'));
+        assert_eq!(g.set((2, 1), 'y'), Err('y'));
+        assert_eq!(g.col(1).collect::<String>(), "bx");
+    }
+}
@@ -0,0 +1,218 @@
+//! Answers already submitted, so `aoc submit` never sends one the site is
+//! known to reject.
+//!
+//! The history is a text file with a guess per line, appended to as they
+//! are made:
+//!
+//! ```text
+//! # year day part outcome answer
+//! 2023 01 1 high 55123
+//! 2023 01 1 right 54239
+//! ```
+//!
+//! The outcome is `right`, `wrong`, or `high`/`low` when the site said the
+//! answer was too high or too low. Those bound all later guesses: one at or
+//! beyond a known bound is as wrong as a repeat.
+
+use std::fmt::{self, Display};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::Part;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Right => write!(f, "right"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::TooHigh => write!(f, "high"),
+            Outcome::TooLow => write!(f, "low"),
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "right" => Ok(Outcome::Right),
+            "wrong" => Ok(Outcome::Wrong),
+            "high" => Ok(Outcome::TooHigh),
+            "low" => Ok(Outcome::TooLow),
+            s => Err(format!("invalid outcome {:?}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Guess {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub outcome: Outcome,
+    pub answer: String,
+}
+
+/// Why a guess isn't worth submitting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    /// The part is solved already, with this answer.
+    Solved(String),
+    /// The same answer was submitted before and was wrong.
+    Repeated(Outcome),
+    /// The answer isn't strictly between what was too low and what was too
+    /// high.
+    OutOfBounds {
+        low: Option<i128>,
+        high: Option<i128>,
+    },
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rejection::Solved(answer) => write!(f, "already solved, the answer is {}", answer),
+            Rejection::Repeated(Outcome::Wrong) => write!(f, "already submitted, it was wrong"),
+            Rejection::Repeated(outcome) => {
+                write!(f, "already submitted, it was too {}", outcome)
+            }
+            Rejection::OutOfBounds { low, high } => match (low, high) {
+                (Some(low), Some(high)) => {
+                    write!(f, "the answer must be between {} and {}", low, high)
+                }
+                (Some(low), None) => write!(f, "the answer must be above {}", low),
+                (None, Some(high)) => write!(f, "the answer must be below {}", high),
+                (None, None) => write!(f, "the answer is out of bounds"),
+            },
+        }
+    }
+}
+
+/// The guesses of a history file, see the [module docs](self).
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    path: Option<PathBuf>,
+    guesses: Vec<Guess>,
+}
+
+impl History {
+    /// A history that isn't kept anywhere.
+    pub fn new() -> History {
+        History::default()
+    }
+
+    /// Reads the history at `path`, which needn't exist yet. Guesses
+    /// recorded from then on are appended to it.
+    pub fn load(path: &Path) -> Result<History, String> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("failed to read {}: {}", path.display(), e)),
+        };
+        let guesses = parse(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(History {
+            path: Some(path.to_path_buf()),
+            guesses,
+        })
+    }
+
+    pub fn guesses(&self) -> &[Guess] {
+        &self.guesses
+    }
+
+    /// Whether `answer` could still be right, going by earlier guesses.
+    pub fn check(&self, year: u16, day: u8, part: Part, answer: &str) -> Result<(), Rejection> {
+        let guesses = self
+            .guesses
+            .iter()
+            .filter(|g| (g.year, g.day, g.part) == (year, day, part))
+            .collect::<Vec<_>>();
+        if let Some(right) = guesses.iter().find(|g| g.outcome == Outcome::Right) {
+            return Err(Rejection::Solved(right.answer.clone()));
+        }
+        if let Some(guess) = guesses.iter().find(|g| g.answer == answer) {
+            return Err(Rejection::Repeated(guess.outcome));
+        }
+
+        let bound = |outcome: Outcome| {
+            guesses
+                .iter()
+                .filter(move |g| g.outcome == outcome)
+                .filter_map(|g| g.answer.parse::<i128>().ok())
+        };
+        let low = bound(Outcome::TooLow).max();
+        let high = bound(Outcome::TooHigh).min();
+        // an answer that isn't a number can't be compared, the site will say
+        match answer.parse::<i128>() {
+            Ok(n) if low.is_some_and(|low| n <= low) || high.is_some_and(|high| n >= high) => {
+                Err(Rejection::OutOfBounds { low, high })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Adds `guess`, appending it to the file if there is one.
+    pub fn record(&mut self, guess: Guess) -> io::Result<()> {
+        if let Some(path) = &self.path {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(
+                file,
+                "{} {:02} {} {} {}",
+                guess.year, guess.day, guess.part, guess.outcome, guess.answer
+            )?;
+        }
+        self.guesses.push(guess);
+        Ok(())
+    }
+}
+
+/// Parses the contents of a history file.
+pub fn parse(text: &str) -> Result<Vec<Guess>, String> {
+    let mut guesses = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let err = |msg: String| format!("line {}: {}", i + 1, msg);
+        let mut fields = line.splitn(5, char::is_whitespace);
+        let (Some(year), Some(day), Some(part), Some(outcome), Some(answer)) = (
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+        ) else {
+            return Err(err(format!(
+                "expected `year day part outcome answer`, got {:?}",
+                line
+            )));
+        };
+        guesses.push(Guess {
+            year: year
+                .parse()
+                .map_err(|_| err(format!("invalid year {:?}", year)))?,
+            day: day
+                .parse()
+                .map_err(|_| err(format!("invalid day {:?}", day)))?,
+            part: part.parse().map_err(err)?,
+            outcome: outcome.parse().map_err(err)?,
+            answer: answer.trim().to_string(),
+        });
+    }
+    Ok(guesses)
+}
@@ -0,0 +1,177 @@
+//! Just enough HTTP/1.1 to talk to adventofcode.com.
+//!
+//! std has no TLS, so `https` URLs go through the `curl` binary, which gets
+//! its whole request on stdin to keep cookies off the command line. Plain
+//! `http` is spoken directly over a [`TcpStream`], which is all the local
+//! stand-in of [`crate::mock`] needs; responses must then come with a
+//! `Content-Length` or end with the connection, not in chunks.
+
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    /// The first header called `name`, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+pub fn get(url: &str, headers: &[(&str, &str)]) -> io::Result<Response> {
+    send("GET", url, headers, None)
+}
+
+/// Posts `form` URL-encoded, like a browser submitting a form.
+pub fn post_form(
+    url: &str,
+    headers: &[(&str, &str)],
+    form: &[(&str, &str)],
+) -> io::Result<Response> {
+    let body = form
+        .iter()
+        .map(|(k, v)| format!("{}={}", url_encode(k), url_encode(v)))
+        .collect::<Vec<_>>()
+        .join("&");
+    let mut headers = headers.to_vec();
+    headers.push(("Content-Type", "application/x-www-form-urlencoded"));
+    send("POST", url, &headers, Some(&body))
+}
+
+fn send(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> io::Result<Response> {
+    if url.starts_with("https://") {
+        return send_curl(method, url, headers, body);
+    }
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| invalid(format!("unsupported URL {:?}", url)))?;
+    let (host, path) = match rest.find('/') {
+        Some(i) => rest.split_at(i),
+        None => (rest, "/"),
+    };
+    let addr = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+
+    let mut stream = TcpStream::connect(addr)?;
+    stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+    let mut request = format!("{} {} HTTP/1.1\r\nHost: {}\r\n", method, path, host);
+    for (name, value) in headers {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    let body = body.unwrap_or("");
+    request.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    ));
+    stream.write_all(request.as_bytes())?;
+
+    let mut raw = vec![];
+    stream.read_to_end(&mut raw)?;
+    parse_response(&String::from_utf8_lossy(&raw))
+}
+
+fn send_curl(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> io::Result<Response> {
+    // a curl config file, see `curl --config`
+    let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+    let mut config = format!(
+        "silent\nshow-error\ninclude\nmax-time = 30\nrequest = {}\nurl = {}\n",
+        quote(method),
+        quote(url)
+    );
+    for (name, value) in headers {
+        config.push_str(&format!(
+            "header = {}\n",
+            quote(&format!("{}: {}", name, value))
+        ));
+    }
+    if let Some(body) = body {
+        config.push_str(&format!("data-binary = {}\n", quote(body)));
+    }
+
+    let mut child = Command::new("curl")
+        .args(["--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| io::Error::new(e.kind(), format!("failed to run curl: {}", e)))?;
+    child.stdin.take().unwrap().write_all(config.as_bytes())?;
+    let out = child.wait_with_output()?;
+    if !out.status.success() {
+        return Err(io::Error::other(format!(
+            "curl failed: {}",
+            String::from_utf8_lossy(&out.stderr).trim()
+        )));
+    }
+    parse_response(&String::from_utf8_lossy(&out.stdout))
+}
+
+// Splits a response into its status, headers and body, skipping any
+// `100 Continue` that came first.
+fn parse_response(raw: &str) -> io::Result<Response> {
+    let mut rest = raw;
+    loop {
+        let (head, body) = rest
+            .split_once("\r\n\r\n")
+            .ok_or_else(|| invalid("incomplete HTTP response".to_string()))?;
+        let mut lines = head.lines();
+        let status_line = lines.next().unwrap_or("");
+        let status = status_line
+            .split_whitespace()
+            .nth(1)
+            .and_then(|s| s.parse::<u16>().ok())
+            .ok_or_else(|| invalid(format!("invalid HTTP status line {:?}", status_line)))?;
+        if (100..200).contains(&status) {
+            rest = body;
+            continue;
+        }
+        let headers = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(n, v)| (n.trim().to_string(), v.trim().to_string()))
+            .collect();
+        return Ok(Response {
+            status,
+            headers,
+            body: body.to_string(),
+        });
+    }
+}
+
+fn url_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
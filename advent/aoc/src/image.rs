@@ -0,0 +1,966 @@
+//! Pictures of grids and point sets as PPM, PNG and animated GIF files,
+//! written with nothing but std.
+//!
+//! A [`Frame`] stores a colour index per pixel and the colours themselves
+//! live in a [`Palette`], which is how PNG and GIF store pictures anyway and
+//! lets a whole animation share one set of colours. Simulations can push
+//! their frames to any [`Animation`]: a [`Gif`] or a [`FrameDir`] of
+//! numbered pictures.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{Grid, Point};
+
+pub type Rgb = [u8; 3];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Palette {
+    colors: Vec<Rgb>,
+}
+
+impl Palette {
+    /// Panics unless there are between 1 and 256 colours.
+    pub fn new(colors: Vec<Rgb>) -> Palette {
+        assert!(
+            (1..=256).contains(&colors.len()),
+            "a palette holds 1 to 256 colours, not {}",
+            colors.len()
+        );
+        Palette { colors }
+    }
+
+    /// Black and white, for `bool` cells.
+    pub fn mono() -> Palette {
+        Palette::new(vec![[0, 0, 0], [255, 255, 255]])
+    }
+
+    /// `n` colours going evenly from `from` to `to`, e.g. for digit cells.
+    pub fn gradient(from: Rgb, to: Rgb, n: usize) -> Palette {
+        let colors = (0..n)
+            .map(|i| {
+                let t = if n > 1 {
+                    i as f64 / (n - 1) as f64
+                } else {
+                    0.0
+                };
+                std::array::from_fn(|c| {
+                    (from[c] as f64 + (to[c] as f64 - from[c] as f64) * t).round() as u8
+                })
+            })
+            .collect();
+        Palette::new(colors)
+    }
+
+    pub fn colors(&self) -> &[Rgb] {
+        &self.colors
+    }
+}
+
+/// A picture as a palette index per pixel, row by row from the top left.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Frame {
+    /// A picture in colour 0.
+    pub fn new(width: usize, height: usize) -> Frame {
+        Frame {
+            width,
+            height,
+            pixels: vec![0; width * height],
+        }
+    }
+
+    /// A pixel per cell, coloured by `f`.
+    pub fn from_grid<T>(grid: &Grid<T>, mut f: impl FnMut(&T) -> u8) -> Frame {
+        Frame {
+            width: grid.cols(),
+            height: grid.rows(),
+            pixels: grid.cells().iter().map(&mut f).collect(),
+        }
+    }
+
+    /// `points` in `color` on colour 0, with `y` going down and cropped to
+    /// the points.
+    pub fn from_points(points: impl IntoIterator<Item = Point>, color: u8) -> Frame {
+        let points = points.into_iter().collect::<Vec<_>>();
+        let min_x = points.iter().map(|p| p.x).min().unwrap_or(0);
+        let min_y = points.iter().map(|p| p.y).min().unwrap_or(0);
+        let width = points.iter().map(|p| p.x - min_x + 1).max().unwrap_or(0);
+        let height = points.iter().map(|p| p.y - min_y + 1).max().unwrap_or(0);
+        let mut frame = Frame::new(width as usize, height as usize);
+        for p in points {
+            frame.set((p.x - min_x) as usize, (p.y - min_y) as usize, color);
+        }
+        frame
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        assert!(x < self.width && y < self.height);
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: u8) {
+        assert!(x < self.width && y < self.height);
+        self.pixels[y * self.width + x] = color;
+    }
+
+    /// Every pixel blown up into a `k` by `k` square, since a pixel per cell
+    /// is too small to see.
+    pub fn scaled(&self, k: usize) -> Frame {
+        let (width, height) = (self.width * k, self.height * k);
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            let wide = row
+                .iter()
+                .flat_map(|&c| std::iter::repeat_n(c, k))
+                .collect::<Vec<_>>();
+            for _ in 0..k {
+                pixels.extend_from_slice(&wide);
+            }
+        }
+        Frame {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Writes a binary PPM, the simplest format most viewers open.
+    pub fn write_ppm(&self, palette: &Palette, mut out: impl Write) -> io::Result<()> {
+        self.check(palette)?;
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        let rgb = self
+            .pixels
+            .iter()
+            .flat_map(|&c| palette.colors[c as usize])
+            .collect::<Vec<_>>();
+        out.write_all(&rgb)
+    }
+
+    /// Writes an 8-bit paletted PNG.
+    pub fn write_png(&self, palette: &Palette, mut out: impl Write) -> io::Result<()> {
+        self.check(palette)?;
+        out.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+        let mut header = vec![];
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // 8 bits per pixel, paletted, and the only compression, filtering
+        // and (no) interlacing there are
+        header.extend([8, 3, 0, 0, 0]);
+        png_chunk(&mut out, b"IHDR", &header)?;
+        png_chunk(&mut out, b"PLTE", palette.colors.concat().as_slice())?;
+
+        // every row starts with its filter, 0 for none
+        let mut raw = Vec::with_capacity((self.width + 1) * self.height);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+        png_chunk(&mut out, b"IDAT", &zlib(&raw, self.width + 1))?;
+        png_chunk(&mut out, b"IEND", &[])
+    }
+
+    /// Saves the picture in the format named by the extension of `path`:
+    /// `ppm`, `png` or a one frame `gif`.
+    pub fn save(&self, palette: &Palette, path: &Path) -> io::Result<()> {
+        let format = path.extension().and_then(|e| e.to_str());
+        if !matches!(format, Some("ppm" | "png" | "gif")) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("can't tell the image format of {}", path.display()),
+            ));
+        }
+        let mut out = BufWriter::new(File::create(path)?);
+        match format {
+            Some("ppm") => self.write_ppm(palette, &mut out)?,
+            Some("png") => self.write_png(palette, &mut out)?,
+            _ => {
+                let mut gif = Gif::new(&mut out, palette, self.width, self.height, 0)?;
+                gif.push(self)?;
+                gif.finish()?;
+            }
+        }
+        out.flush()
+    }
+
+    fn check(&self, palette: &Palette) -> io::Result<()> {
+        match self
+            .pixels
+            .iter()
+            .find(|&&c| c as usize >= palette.colors.len())
+        {
+            Some(c) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("colour {} is missing from the palette", c),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Somewhere to put the frames of an animation, one at a time.
+pub trait Animation {
+    fn push(&mut self, frame: &Frame) -> io::Result<()>;
+}
+
+/// An animated GIF that loops forever. Every frame must have the size given
+/// to [`Gif::new`] and [`Gif::finish`] must be called to end the file.
+pub struct Gif<W: Write> {
+    out: W,
+    palette: Palette,
+    width: usize,
+    height: usize,
+    delay: u16,
+    code_size: u8,
+}
+
+impl<W: Write> Gif<W> {
+    /// Starts a GIF showing each frame for `delay` hundredths of a second.
+    pub fn new(
+        mut out: W,
+        palette: &Palette,
+        width: usize,
+        height: usize,
+        delay: u16,
+    ) -> io::Result<Gif<W>> {
+        let too_big = |n: usize| {
+            u16::try_from(n).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidInput, "a GIF is at most 65535 wide")
+            })
+        };
+        let (w, h) = (too_big(width)?, too_big(height)?);
+        // the colour table holds a power of two colours, at least 2
+        let bits = (usize::BITS - (palette.colors.len() - 1).leading_zeros()).max(1);
+        out.write_all(b"GIF89a")?;
+        out.write_all(&w.to_le_bytes())?;
+        out.write_all(&h.to_le_bytes())?;
+        out.write_all(&[0xf0 | (bits - 1) as u8, 0, 0])?;
+        for i in 0..1 << bits {
+            out.write_all(palette.colors.get(i).unwrap_or(&[0, 0, 0]))?;
+        }
+        // the application extension that makes browsers loop forever
+        out.write_all(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+        Ok(Gif {
+            out,
+            palette: palette.clone(),
+            width,
+            height,
+            delay,
+            code_size: bits.max(2) as u8,
+        })
+    }
+
+    /// Ends the file and hands back the writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.out.write_all(b"\x3b")?;
+        Ok(self.out)
+    }
+}
+
+impl<W: Write> Animation for Gif<W> {
+    fn push(&mut self, frame: &Frame) -> io::Result<()> {
+        frame.check(&self.palette)?;
+        if (frame.width, frame.height) != (self.width, self.height) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "a {}x{} frame in a {}x{} GIF",
+                    frame.width, frame.height, self.width, self.height
+                ),
+            ));
+        }
+        let out = &mut self.out;
+        out.write_all(b"\x21\xf9\x04\x00")?;
+        out.write_all(&self.delay.to_le_bytes())?;
+        out.write_all(b"\x00\x00\x2c\x00\x00\x00\x00")?;
+        out.write_all(&(self.width as u16).to_le_bytes())?;
+        out.write_all(&(self.height as u16).to_le_bytes())?;
+        out.write_all(&[0, self.code_size])?;
+        for block in lzw(&frame.pixels, self.code_size).chunks(255) {
+            out.write_all(&[block.len() as u8])?;
+            out.write_all(block)?;
+        }
+        out.write_all(&[0])
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+}
+
+/// A directory getting one picture per frame, named `00000.png`,
+/// `00001.png` and so on.
+pub struct FrameDir {
+    dir: PathBuf,
+    palette: Palette,
+    format: ImageFormat,
+    count: usize,
+}
+
+impl FrameDir {
+    /// Creates `dir` if needed. Pictures already there get overwritten.
+    pub fn create(dir: &Path, palette: &Palette, format: ImageFormat) -> io::Result<FrameDir> {
+        fs::create_dir_all(dir)?;
+        Ok(FrameDir {
+            dir: dir.to_path_buf(),
+            palette: palette.clone(),
+            format,
+            count: 0,
+        })
+    }
+
+    /// The number of frames written so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl Animation for FrameDir {
+    fn push(&mut self, frame: &Frame) -> io::Result<()> {
+        let ext = match self.format {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Png => "png",
+        };
+        let path = self.dir.join(format!("{:05}.{}", self.count, ext));
+        frame.save(&self.palette, &path)?;
+        self.count += 1;
+        Ok(())
+    }
+}
+
+// Bits packed into bytes from the least significant end, as both deflate
+// and GIF do.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u32,
+    len: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, bits: u32) {
+        self.acc |= value << self.len;
+        self.len += bits;
+        while self.len >= 8 {
+            self.bytes.push(self.acc as u8);
+            self.acc >>= 8;
+            self.len -= 8;
+        }
+    }
+
+    // Huffman codes go in starting from their most significant bit.
+    fn write_code(&mut self, code: u32, bits: u32) {
+        self.write(code.reverse_bits() >> (32 - bits), bits);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.bytes.push(self.acc as u8);
+        }
+        self.bytes
+    }
+}
+
+const LENGTH_BASE: [u32; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u32; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u32; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u32; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+// A zlib stream of one deflate block with the fixed Huffman codes. Repeats
+// are only looked for one byte back and one row of `stride` bytes back,
+// which is where pictures of grids repeat themselves, especially scaled up.
+fn zlib(data: &[u8], stride: usize) -> Vec<u8> {
+    let mut bits = BitWriter::default();
+    // CMF and FLG: deflate with a 32K window, no dictionary, and a check sum
+    bits.write(0x0178, 16);
+    // the last block, with fixed codes
+    bits.write(0b11, 3);
+
+    let symbol = |bits: &mut BitWriter, sym: u32| match sym {
+        0..=143 => bits.write_code(0x30 + sym, 8),
+        144..=255 => bits.write_code(0x190 + sym - 144, 9),
+        256..=279 => bits.write_code(sym - 256, 7),
+        _ => bits.write_code(0xc0 + sym - 280, 8),
+    };
+    let mut i = 0;
+    while i < data.len() {
+        let longest = [1, stride]
+            .into_iter()
+            .filter(|&d| d <= i && d <= 32768)
+            .map(|d| {
+                let len = (0..258.min(data.len() - i))
+                    .take_while(|&k| data[i + k] == data[i + k - d])
+                    .count();
+                (len, d)
+            })
+            .max();
+        match longest {
+            Some((len, dist)) if len >= 3 => {
+                let (len, dist) = (len as u32, dist as u32);
+                let code = LENGTH_BASE.iter().rposition(|&b| b <= len).unwrap();
+                symbol(&mut bits, 257 + code as u32);
+                bits.write(len - LENGTH_BASE[code], LENGTH_EXTRA[code]);
+                let code = DIST_BASE.iter().rposition(|&b| b <= dist).unwrap();
+                bits.write_code(code as u32, 5);
+                bits.write(dist - DIST_BASE[code], DIST_EXTRA[code]);
+                i += len as usize;
+            }
+            _ => {
+                symbol(&mut bits, data[i] as u32);
+                i += 1;
+            }
+        }
+    }
+    symbol(&mut bits, 256);
+
+    let mut out = bits.finish();
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    b << 16 | a
+}
+
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 {
+                0xedb88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+fn png_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    let crc = !kind.iter().chain(data).fold(!0u32, |crc, &byte| {
+        CRC_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8)
+    });
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    out.write_all(&crc.to_be_bytes())
+}
+
+// GIF's variant of LZW: codes start a bit wider than the pixels and grow up
+// to 12 bits, after which the table starts over.
+fn lzw(pixels: &[u8], min_size: u8) -> Vec<u8> {
+    let clear = 1u32 << min_size;
+    let end = clear + 1;
+    let mut bits = BitWriter::default();
+    let mut table = std::collections::HashMap::new();
+    let mut size = min_size as u32 + 1;
+    let mut next = end + 1;
+    bits.write(clear, size);
+
+    let Some((&first, rest)) = pixels.split_first() else {
+        bits.write(end, size);
+        return bits.finish();
+    };
+    let mut prefix = first as u32;
+    for &pixel in rest {
+        if let Some(&code) = table.get(&(prefix, pixel)) {
+            prefix = code;
+            continue;
+        }
+        bits.write(prefix, size);
+        table.insert((prefix, pixel), next);
+        next += 1;
+        // the decoder adds each code a step later, so it widens on the
+        // code after this
+        if next > 1 << size && size < 12 {
+            size += 1;
+        }
+        if next == 4096 {
+            bits.write(clear, size);
+            table.clear();
+            size = min_size as u32 + 1;
+            next = end + 1;
+        }
+        prefix = pixel as u32;
+    }
+    bits.write(prefix, size);
+    // the decoder adds a code for that last one too, which may widen it
+    if next == 1 << size && size < 12 {
+        size += 1;
+    }
+    bits.write(end, size);
+    bits.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reads bits the way BitWriter writes them.
+    struct BitReader<'a> {
+        bytes: &'a [u8],
+        pos: usize,
+    }
+
+    impl BitReader<'_> {
+        fn bit(&mut self) -> u32 {
+            let bit = self.bytes[self.pos / 8] >> (self.pos % 8) & 1;
+            self.pos += 1;
+            bit as u32
+        }
+
+        fn read(&mut self, bits: u32) -> u32 {
+            (0..bits).fold(0, |value, i| value | self.bit() << i)
+        }
+
+        fn read_code(&mut self, bits: u32) -> u32 {
+            (0..bits).fold(0, |code, _| code << 1 | self.bit())
+        }
+    }
+
+    // A deflate decoder for what zlib writes, fixed Huffman blocks, and
+    // stored ones besides.
+    fn inflate(data: &[u8]) -> Vec<u8> {
+        let mut bits = BitReader {
+            bytes: data,
+            pos: 0,
+        };
+        let mut out = Vec::new();
+        loop {
+            let last = bits.read(1) == 1;
+            match bits.read(2) {
+                0 => {
+                    let start = bits.pos.div_ceil(8);
+                    let len = u16::from_le_bytes([data[start], data[start + 1]]);
+                    let nlen = u16::from_le_bytes([data[start + 2], data[start + 3]]);
+                    assert_eq!(len, !nlen);
+                    out.extend_from_slice(&data[start + 4..start + 4 + len as usize]);
+                    bits.pos = (start + 4 + len as usize) * 8;
+                }
+                1 => loop {
+                    let sym = match bits.read_code(7) {
+                        code @ 0..=0x17 => 256 + code,
+                        code => match code << 1 | bits.bit() {
+                            code @ 0x30..=0xbf => code - 0x30,
+                            code @ 0xc0..=0xc7 => 280 + code - 0xc0,
+                            code => 144 + (code << 1 | bits.bit()) - 0x190,
+                        },
+                    };
+                    match sym {
+                        0..=255 => out.push(sym as u8),
+                        256 => break,
+                        _ => {
+                            let i = sym as usize - 257;
+                            let len = LENGTH_BASE[i] + bits.read(LENGTH_EXTRA[i]);
+                            let i = bits.read_code(5) as usize;
+                            let dist = DIST_BASE[i] + bits.read(DIST_EXTRA[i]);
+                            for _ in 0..len {
+                                out.push(out[out.len() - dist as usize]);
+                            }
+                        }
+                    }
+                },
+                btype => panic!("unexpected block type {}", btype),
+            }
+            if last {
+                return out;
+            }
+        }
+    }
+
+    fn unzlib(data: &[u8]) -> Vec<u8> {
+        assert_eq!(data[0] & 0x0f, 8, "not deflate");
+        assert_eq!(u16::from_be_bytes([data[0], data[1]]) % 31, 0);
+        let (body, check) = data[2..].split_at(data.len() - 6);
+        let out = inflate(body);
+        assert_eq!(adler32(&out).to_be_bytes(), check);
+        out
+    }
+
+    // Pseudorandom bytes below `n`.
+    fn noise(len: usize, n: u8) -> Vec<u8> {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state % n as u64) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn zlib_round_trip() {
+        assert_eq!(unzlib(&zlib(&[], 1)), []);
+        assert_eq!(unzlib(&zlib(b"a", 1)), b"a");
+        // runs longer than one repeat can copy
+        let run = vec![7; 1000];
+        let packed = zlib(&run, 1);
+        assert!(packed.len() < 50);
+        assert_eq!(unzlib(&packed), run);
+        // rows repeating a row back, every byte value, and a stride past the
+        // window that mustn't be used
+        let row = (0..=255).collect::<Vec<u8>>();
+        let rows = row.repeat(20);
+        assert_eq!(unzlib(&zlib(&rows, 256)), rows);
+        for (len, stride) in [(5000, 1), (5000, 37), (70000, 33000)] {
+            let data = noise(len, 3);
+            assert_eq!(unzlib(&zlib(&data, stride)), data);
+        }
+    }
+
+    #[test]
+    fn stored_blocks_inflate_too() {
+        // a stored block, and then the fixed one zlib writes
+        let mut stream = vec![0b000, 3, 0, 0xfc, 0xff];
+        stream.extend(b"abc");
+        let packed = zlib(b"xyzxyzxyz", 3);
+        stream.extend(&packed[2..packed.len() - 4]);
+        assert_eq!(inflate(&stream), b"abcxyzxyzxyz");
+        // a last stored block, empty
+        assert_eq!(inflate(&[0b001, 0, 0, 0xff, 0xff]), []);
+    }
+
+    #[test]
+    fn adler32_sums() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        // long enough to need the modulus
+        let data = vec![0xff; 100_000];
+        let (a, b) = data.iter().fold((1u64, 0u64), |(a, b), &x| {
+            let a = (a + x as u64) % 65521;
+            (a, (b + a) % 65521)
+        });
+        assert_eq!(adler32(&data), (b << 16 | a) as u32);
+    }
+
+    // The IHDR fields, palette and pixels of a PNG written by write_png.
+    fn decode_png(png: &[u8]) -> (Vec<u8>, Vec<Rgb>, Frame) {
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        let mut rest = &png[8..];
+        let mut chunks = Vec::new();
+        while !rest.is_empty() {
+            let len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            let (kind, data) = (&rest[4..8], &rest[8..8 + len]);
+            let crc = u32::from_be_bytes(rest[8 + len..12 + len].try_into().unwrap());
+            // bit by bit, not with the table
+            let mut check = !0u32;
+            for &byte in kind.iter().chain(data) {
+                check ^= byte as u32;
+                for _ in 0..8 {
+                    check = check >> 1 ^ (0xedb8_8320 & (check & 1).wrapping_neg());
+                }
+            }
+            assert_eq!(!check, crc);
+            chunks.push((kind, data));
+            rest = &rest[12 + len..];
+        }
+        let kinds = chunks.iter().map(|c| c.0).collect::<Vec<_>>();
+        assert_eq!(kinds, [b"IHDR", b"PLTE", b"IDAT", b"IEND"]);
+
+        let header = chunks[0].1;
+        let width = u32::from_be_bytes(header[..4].try_into().unwrap()) as usize;
+        let height = u32::from_be_bytes(header[4..8].try_into().unwrap()) as usize;
+        let colors = chunks[1].1.chunks(3).map(|c| [c[0], c[1], c[2]]).collect();
+        let raw = unzlib(chunks[2].1);
+        assert_eq!(raw.len(), (width + 1) * height);
+        let mut pixels = Vec::new();
+        for row in raw.chunks(width + 1) {
+            assert_eq!(row[0], 0, "filtered row");
+            pixels.extend_from_slice(&row[1..]);
+        }
+        let frame = Frame {
+            width,
+            height,
+            pixels,
+        };
+        (header[8..].to_vec(), colors, frame)
+    }
+
+    // GIF's LZW, decoded.
+    fn unlzw(data: &[u8], min_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_size;
+        let fresh = || {
+            let mut table = (0..clear).map(|c| vec![c as u8]).collect::<Vec<_>>();
+            // the clear and end codes
+            table.extend([vec![], vec![]]);
+            table
+        };
+        let mut bits = BitReader {
+            bytes: data,
+            pos: 0,
+        };
+        let mut table = fresh();
+        let mut size = min_size as u32 + 1;
+        let mut prev: Option<Vec<u8>> = None;
+        let mut out = Vec::new();
+        loop {
+            let code = bits.read(size) as usize;
+            if code == clear {
+                table = fresh();
+                size = min_size as u32 + 1;
+                prev = None;
+                continue;
+            }
+            if code == clear + 1 {
+                // nothing but padding may follow
+                assert!(data.len() * 8 - bits.pos < 8);
+                return out;
+            }
+            let entry = match (table.get(code), &prev) {
+                (Some(entry), _) => entry.clone(),
+                // the code about to be added, which starts like it ends
+                (None, Some(prev)) if code == table.len() => {
+                    let mut entry = prev.clone();
+                    entry.push(prev[0]);
+                    entry
+                }
+                _ => panic!("code {} out of nowhere", code),
+            };
+            if let Some(mut prev) = prev.take() {
+                prev.push(entry[0]);
+                table.push(prev);
+            }
+            out.extend_from_slice(&entry);
+            prev = Some(entry);
+            if table.len() == 1 << size && size < 12 {
+                size += 1;
+            }
+        }
+    }
+
+    // The palette, delays and frames of a GIF written by Gif.
+    fn decode_gif(gif: &[u8]) -> (Vec<Rgb>, Vec<u16>, Vec<Frame>) {
+        assert_eq!(&gif[..6], b"GIF89a");
+        let width = u16::from_le_bytes([gif[6], gif[7]]) as usize;
+        let height = u16::from_le_bytes([gif[8], gif[9]]) as usize;
+        assert_eq!(gif[10] & 0xf8, 0xf0);
+        let n = 2 << (gif[10] & 7);
+        let colors = gif[13..13 + 3 * n]
+            .chunks(3)
+            .map(|c| [c[0], c[1], c[2]])
+            .collect();
+        let mut rest = &gif[13 + 3 * n..];
+        let netscape = b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00";
+        rest = rest.strip_prefix(netscape).unwrap();
+
+        let (mut delays, mut frames) = (vec![], vec![]);
+        while rest != b"\x3b" {
+            assert_eq!(&rest[..4], b"\x21\xf9\x04\x00");
+            delays.push(u16::from_le_bytes([rest[4], rest[5]]));
+            assert_eq!(&rest[6..12], b"\x00\x00\x2c\x00\x00\x00");
+            assert_eq!(rest[12], 0);
+            let w = u16::from_le_bytes([rest[13], rest[14]]) as usize;
+            let h = u16::from_le_bytes([rest[15], rest[16]]) as usize;
+            assert_eq!((w, h), (width, height));
+            assert_eq!(rest[17], 0, "local colour table or interlacing");
+            let min_size = rest[18];
+            rest = &rest[19..];
+            let mut data = Vec::new();
+            while rest[0] != 0 {
+                let len = rest[0] as usize;
+                data.extend_from_slice(&rest[1..1 + len]);
+                rest = &rest[1 + len..];
+            }
+            rest = &rest[1..];
+            let pixels = unlzw(&data, min_size);
+            assert_eq!(pixels.len(), width * height);
+            frames.push(Frame {
+                width,
+                height,
+                pixels,
+            });
+        }
+        (colors, delays, frames)
+    }
+
+    fn frame(width: usize, pixels: Vec<u8>) -> Frame {
+        Frame {
+            width,
+            height: pixels.len() / width,
+            pixels,
+        }
+    }
+
+    #[test]
+    fn png_round_trip() {
+        let palette = Palette::gradient([0, 0, 0], [255, 128, 0], 5);
+        let frame = frame(7, noise(7 * 5, 5)).scaled(3);
+        let mut png = vec![];
+        frame.write_png(&palette, &mut png).unwrap();
+        let (header, colors, decoded) = decode_png(&png);
+        assert_eq!(header, [8, 3, 0, 0, 0]);
+        assert_eq!(colors, palette.colors());
+        assert_eq!(decoded, frame);
+
+        // a colour the palette doesn't have
+        let frame = Frame::from_points([Point::new(0, 0)], 2);
+        let e = frame.write_png(&Palette::mono(), &mut vec![]).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn gif_round_trip() {
+        let palette = Palette::gradient([0, 0, 0], [255, 255, 255], 4);
+        // a run of one colour codes KwKwK at once, and noise runs through
+        // the 4096 codes more than once
+        let frames = [
+            frame(50, vec![3; 2500]),
+            frame(50, noise(2500, 4)),
+            frame(300, noise(300 * 200, 4)),
+        ];
+        for f in &frames {
+            let mut gif = Gif::new(vec![], &palette, f.width, f.height, 7).unwrap();
+            gif.push(f).unwrap();
+            gif.push(&Frame::new(f.width, f.height)).unwrap();
+            let (colors, delays, decoded) = decode_gif(&gif.finish().unwrap());
+            assert_eq!(colors, palette.colors());
+            assert_eq!(delays, [7, 7]);
+            assert_eq!(decoded, [f.clone(), Frame::new(f.width, f.height)]);
+        }
+
+        // every code size, from a one colour palette on
+        for n in [1, 2, 3, 5, 16, 17, 255, 256] {
+            let palette = Palette::gradient([0, 0, 0], [255, 0, 0], n);
+            let frame = frame(64, noise(64 * 64, n.min(255) as u8));
+            let mut gif = Gif::new(vec![], &palette, 64, 64, 0).unwrap();
+            gif.push(&frame).unwrap();
+            let (colors, _, decoded) = decode_gif(&gif.finish().unwrap());
+            assert_eq!(colors.len(), n.next_power_of_two().max(2));
+            assert_eq!(&colors[..n], palette.colors());
+            assert_eq!(decoded, [frame]);
+        }
+    }
+
+    #[test]
+    fn gif_takes_frames_of_its_size() {
+        let mut gif = Gif::new(vec![], &Palette::mono(), 4, 3, 0).unwrap();
+        let e = gif.push(&Frame::new(3, 4)).unwrap_err();
+        assert_eq!(e.to_string(), "a 3x4 frame in a 4x3 GIF");
+        let (_, _, frames) = decode_gif(&gif.finish().unwrap());
+        assert!(frames.is_empty());
+        assert!(Gif::new(vec![], &Palette::mono(), 70000, 1, 0).is_err());
+    }
+
+    #[test]
+    fn frame_from_points() {
+        let points = [Point::new(-2, 1), Point::new(1, 3), Point::new(0, 1)];
+        let frame = Frame::from_points(points, 1);
+        assert_eq!((frame.width(), frame.height()), (4, 3));
+        let lit = (0..3)
+            .flat_map(|y| (0..4).map(move |x| (x, y)))
+            .filter(|&(x, y)| frame.get(x, y) == 1)
+            .collect::<Vec<_>>();
+        assert_eq!(lit, [(0, 0), (2, 0), (3, 2)]);
+        assert_eq!(Frame::from_points([], 1), Frame::new(0, 0));
+    }
+
+    #[test]
+    fn scaled_frames() {
+        let f = frame(2, vec![0, 1, 2, 3]).scaled(2);
+        assert_eq!((f.width(), f.height()), (4, 4));
+        assert_eq!(f.pixels, [0, 0, 1, 1, 0, 0, 1, 1, 2, 2, 3, 3, 2, 2, 3, 3]);
+        assert_eq!(frame(2, vec![0, 1]).scaled(0), Frame::new(0, 0));
+    }
+
+    // A fresh directory per test, under the system's temporary one.
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn save_by_extension() {
+        let dir = scratch_dir("image-save");
+        let palette = Palette::new(vec![[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        let frame = frame(3, vec![0, 1, 2, 2, 1, 0]);
+
+        frame.save(&palette, &dir.join("a.ppm")).unwrap();
+        let ppm = fs::read(dir.join("a.ppm")).unwrap();
+        let mut expected = b"P6\n3 2\n255\n".to_vec();
+        expected.extend([1, 2, 3, 4, 5, 6, 7, 8, 9, 7, 8, 9, 4, 5, 6, 1, 2, 3]);
+        assert_eq!(ppm, expected);
+
+        frame.save(&palette, &dir.join("a.png")).unwrap();
+        let (_, _, decoded) = decode_png(&fs::read(dir.join("a.png")).unwrap());
+        assert_eq!(decoded, frame);
+
+        frame.save(&palette, &dir.join("a.gif")).unwrap();
+        let (_, delays, decoded) = decode_gif(&fs::read(dir.join("a.gif")).unwrap());
+        assert_eq!(delays, [0]);
+        assert_eq!(decoded, std::slice::from_ref(&frame));
+
+        let e = frame.save(&palette, &dir.join("a.bmp")).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+        assert!(!dir.join("a.bmp").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn frame_dir_numbers_frames() {
+        let dir = scratch_dir("image-frames");
+        let frames = [frame(2, vec![0, 1]), frame(2, vec![1, 1])];
+        for (format, ext) in [(ImageFormat::Png, "png"), (ImageFormat::Ppm, "ppm")] {
+            let mut out = FrameDir::create(&dir.join(ext), &Palette::mono(), format).unwrap();
+            for f in &frames {
+                out.push(f).unwrap();
+            }
+            assert_eq!(out.count(), 2);
+            let mut names = fs::read_dir(dir.join(ext))
+                .unwrap()
+                .map(|e| e.unwrap().file_name().into_string().unwrap())
+                .collect::<Vec<_>>();
+            names.sort();
+            assert_eq!(names, [format!("00000.{}", ext), format!("00001.{}", ext)]);
+        }
+        let (_, _, second) = decode_png(&fs::read(dir.join("png/00001.png")).unwrap());
+        assert_eq!(second, frames[1]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Which of a day's input files to load.
+///
+/// Files live next to each other in the year's `input` directory: `09.txt` is
+/// the puzzle input, `09_sample.txt` the first sample and `09_sample2.txt`,
+/// `09_sample3.txt`, ... any further ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Variant {
+    Puzzle,
+    Sample(u32),
+}
+
+impl Variant {
+    /// File names to try for `day`, in order of preference.
+    pub fn file_names(self, day: u8) -> Vec<String> {
+        match self {
+            Variant::Puzzle => vec![format!("{:02}.txt", day)],
+            Variant::Sample(1) => vec![
+                format!("{:02}_sample.txt", day),
+                format!("{:02}_sample1.txt", day),
+            ],
+            Variant::Sample(n) => vec![format!("{:02}_sample{}.txt", day, n)],
+        }
+    }
+}
+
+impl Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Variant::Puzzle => write!(f, "input"),
+            Variant::Sample(1) => write!(f, "sample"),
+            Variant::Sample(n) => write!(f, "sample{}", n),
+        }
+    }
+}
+
+impl FromStr for Variant {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "input" => Ok(Variant::Puzzle),
+            "sample" => Ok(Variant::Sample(1)),
+            s => s
+                .strip_prefix("sample")
+                .and_then(|n| n.parse().ok())
+                .filter(|&n| n > 0)
+                .map(Variant::Sample)
+                .ok_or_else(|| format!("invalid input variant {:?}", s)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// None of the candidate files exist.
+    NotFound {
+        day: u8,
+        tried: Vec<PathBuf>,
+    },
+    Io {
+        path: PathBuf,
+        err: io::Error,
+    },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::NotFound { day, tried } => {
+                write!(f, "no input found for day {}, looked for:", day)?;
+                for path in tried {
+                    write!(f, "\n  {}", path.display())?;
+                }
+                Ok(())
+            }
+            InputError::Io { path, err } => write!(f, "failed to read {}: {}", path.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Returns the path of the first existing file for `day`/`variant` in `dir`.
+pub fn locate(dir: &Path, day: u8, variant: Variant) -> Result<PathBuf, InputError> {
+    let tried: Vec<PathBuf> = variant
+        .file_names(day)
+        .into_iter()
+        .map(|name| dir.join(name))
+        .collect();
+    match tried.iter().find(|path| path.is_file()) {
+        Some(path) => Ok(path.clone()),
+        None => Err(InputError::NotFound { day, tried }),
+    }
+}
+
+/// Reads the input file for `day`/`variant` from `dir`.
+pub fn read(dir: &Path, day: u8, variant: Variant) -> Result<String, InputError> {
+    let path = locate(dir, day, variant)?;
+    fs::read_to_string(&path).map_err(|err| InputError::Io { path, err })
+}
@@ -0,0 +1,359 @@
+//! Half-open ranges of numbers and sets of them, for puzzles that push whole
+//! ranges of values through a series of maps or conditions instead of one
+//! value at a time.
+
+use std::iter::Sum;
+use std::ops::{Add, Sub};
+
+/// The values from `start` up to but excluding `end`. Empty unless
+/// `start < end`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    pub const fn new(start: T, end: T) -> Interval<T> {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.start <= x && x < self.end
+    }
+
+    /// The values in both intervals, `None` if there are none.
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        let common = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!common.is_empty()).then_some(common)
+    }
+
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The values below `x` and the rest, either of which may be empty.
+    pub fn split_at(&self, x: T) -> (Interval<T>, Interval<T>) {
+        let mid = x.max(self.start).min(self.end.max(self.start));
+        (Interval::new(self.start, mid), Interval::new(mid, self.end))
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Interval<T> {
+    /// The `len` values starting at `start`.
+    pub fn from_len(start: T, len: T) -> Interval<T> {
+        Interval::new(start, start + len)
+    }
+
+    /// The number of values, 0 for empty intervals.
+    pub fn len(&self) -> T {
+        self.end.max(self.start) - self.start
+    }
+}
+
+/// A set of values kept as the sorted list of the disjoint intervals making
+/// it up, with adjacent intervals merged, so equal sets compare equal.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { intervals: vec![] }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet::default()
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        // the last interval starting at or before x
+        let i = self.intervals.partition_point(|iv| iv.start <= x);
+        i > 0 && self.intervals[i - 1].contains(x)
+    }
+
+    /// The smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|iv| iv.start)
+    }
+
+    /// One past the largest value in the set.
+    pub fn end(&self) -> Option<T> {
+        self.intervals.last().map(|iv| iv.end)
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        *self = self.union(&IntervalSet::from(interval));
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut all = [&self.intervals[..], &other.intervals[..]].concat();
+        all.sort_unstable();
+        IntervalSet::from_sorted(all)
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let (a, b) = (&self.intervals, &other.intervals);
+        let (mut i, mut j) = (0, 0);
+        let mut intervals = vec![];
+        while i < a.len() && j < b.len() {
+            intervals.extend(a[i].intersection(&b[j]));
+            // whichever ends first can't overlap anything else on the other side
+            if a[i].end < b[j].end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    /// The values in `self` but not in `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = vec![];
+        let mut cuts = other.intervals.iter().peekable();
+        for &iv in &self.intervals {
+            let mut rest = iv;
+            while let Some(cut) = cuts.peek() {
+                if cut.end <= rest.start {
+                    cuts.next();
+                    continue;
+                }
+                if cut.start >= rest.end {
+                    break;
+                }
+                let (below, _) = rest.split_at(cut.start);
+                let (_, above) = rest.split_at(cut.end);
+                if !below.is_empty() {
+                    intervals.push(below);
+                }
+                rest = above;
+                if rest.is_empty() {
+                    break;
+                }
+                // the cut may reach into the next interval too
+                cuts.next();
+            }
+            if !rest.is_empty() {
+                intervals.push(rest);
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    // Normalises intervals sorted by start.
+    fn from_sorted(sorted: Vec<Interval<T>>) -> IntervalSet<T> {
+        let mut intervals: Vec<Interval<T>> = vec![];
+        for iv in sorted.into_iter().filter(|iv| !iv.is_empty()) {
+            match intervals.last_mut() {
+                Some(last) if iv.start <= last.end => last.end = last.end.max(iv.end),
+                _ => intervals.push(iv),
+            }
+        }
+        IntervalSet { intervals }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> IntervalSet<T> {
+    /// The number of values in the set.
+    pub fn len(&self) -> T
+    where
+        T: Sum,
+    {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    /// Applies a piecewise-linear map: each `(source, dest)` piece shifts the
+    /// values of `source` so that `source.start` lands on `dest`, values
+    /// outside every source stay put. Where sources overlap the first one
+    /// wins.
+    pub fn map_linear(&self, pieces: &[(Interval<T>, T)]) -> IntervalSet<T> {
+        let mut rest = self.clone();
+        let mut mapped = vec![];
+        for &(source, dest) in pieces {
+            // x - start + dest rather than x + (dest - start) so unsigned
+            // types can shift downwards
+            let shift = |x: T| x - source.start + dest;
+            let source = IntervalSet::from(source);
+            for iv in rest.intersection(&source).intervals {
+                mapped.push(Interval::new(shift(iv.start), shift(iv.end)));
+            }
+            rest = rest.difference(&source);
+        }
+        rest.union(&mapped.into_iter().collect())
+    }
+}
+
+impl<T: Copy + Ord> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> IntervalSet<T> {
+        IntervalSet::from_sorted(vec![interval])
+    }
+}
+
+/// Collects intervals in any order, overlapping or not.
+impl<T: Copy + Ord> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> IntervalSet<T> {
+        let mut all: Vec<_> = iter.into_iter().collect();
+        all.sort_unstable();
+        IntervalSet::from_sorted(all)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[(u32, u32)]) -> IntervalSet<u32> {
+        ranges.iter().map(|&(a, b)| Interval::new(a, b)).collect()
+    }
+
+    fn ranges(set: &IntervalSet<u32>) -> Vec<(u32, u32)> {
+        set.intervals()
+            .iter()
+            .map(|iv| (iv.start, iv.end))
+            .collect()
+    }
+
+    #[test]
+    fn empty_intervals() {
+        let empty = Interval::new(5, 5);
+        let backwards = Interval::new(7, 3);
+        assert!(empty.is_empty() && backwards.is_empty());
+        assert_eq!((empty.len(), backwards.len()), (0, 0));
+        assert!(!backwards.contains(5));
+        assert_eq!(Interval::new(0, 10).intersection(&empty), None);
+        assert_eq!(Interval::new(0, 3).intersection(&Interval::new(3, 6)), None);
+        assert_eq!(
+            Interval::new(2, 4).split_at(9),
+            (Interval::new(2, 4), Interval::new(4, 4))
+        );
+        assert_eq!(
+            Interval::new(2, 4).split_at(0),
+            (Interval::new(2, 2), Interval::new(2, 4))
+        );
+
+        // they vanish from sets, which compare equal to the empty set
+        assert_eq!(set(&[(5, 5), (7, 3)]), IntervalSet::new());
+        assert!(set(&[(1, 4)]).difference(&set(&[(0, 9)])).is_empty());
+        assert_eq!(IntervalSet::<u32>::new().min(), None);
+        assert_eq!(set(&[]).len(), 0);
+    }
+
+    #[test]
+    fn adjacent_intervals_merge() {
+        assert_eq!(ranges(&set(&[(3, 5), (0, 3), (5, 6)])), [(0, 6)]);
+        assert_eq!(ranges(&set(&[(0, 2), (3, 5)])), [(0, 2), (3, 5)]);
+        let mut s = set(&[(0, 2), (4, 6)]);
+        s.insert(Interval::new(2, 4));
+        assert_eq!(ranges(&s), [(0, 6)]);
+        // and the merged set is the same value as one built in one go
+        assert_eq!(s, set(&[(0, 6)]));
+        assert_eq!(ranges(&set(&[(0, 2)]).union(&set(&[(2, 3)]))), [(0, 3)]);
+        assert_eq!(
+            set(&[(0, 2)]).intersection(&set(&[(2, 3)])),
+            IntervalSet::new()
+        );
+    }
+
+    #[test]
+    fn difference_with_a_cut_spanning_intervals() {
+        let s = set(&[(0, 10), (20, 30), (40, 50)]);
+        // one cut takes the end of the first interval, the gap and the start
+        // of the second
+        assert_eq!(
+            ranges(&s.difference(&set(&[(5, 25)]))),
+            [(0, 5), (25, 30), (40, 50)]
+        );
+        // and here a cut swallows the whole middle interval
+        let cut = set(&[(8, 42), (45, 46)]);
+        assert_eq!(ranges(&s.difference(&cut)), [(0, 8), (42, 45), (46, 50)]);
+        // several cuts inside one interval
+        let cut = set(&[(1, 2), (3, 4), (9, 21)]);
+        assert_eq!(
+            ranges(&s.difference(&cut)),
+            [(0, 1), (2, 3), (4, 9), (21, 30), (40, 50)]
+        );
+    }
+
+    #[test]
+    fn set_operations_match_brute_force() {
+        let cases = [
+            set(&[(0, 10), (20, 30), (40, 50)]),
+            set(&[(5, 25), (45, 46)]),
+            set(&[(0, 1), (9, 21), (29, 41), (49, 64)]),
+            set(&[(10, 20)]),
+            set(&[]),
+        ];
+        let members = |s: &IntervalSet<u32>| (0..64).filter(|&x| s.contains(x)).collect::<Vec<_>>();
+        for a in &cases {
+            for b in &cases {
+                let both = (0..64).filter(|&x| a.contains(x) && b.contains(x));
+                let either = (0..64).filter(|&x| a.contains(x) || b.contains(x));
+                let only_a = (0..64).filter(|&x| a.contains(x) && !b.contains(x));
+                assert_eq!(members(&a.intersection(b)), both.collect::<Vec<_>>());
+                assert_eq!(members(&a.union(b)), either.collect::<Vec<_>>());
+                assert_eq!(members(&a.difference(b)), only_a.collect::<Vec<_>>());
+                assert_eq!(a.difference(b).len() + a.intersection(b).len(), a.len());
+            }
+        }
+    }
+
+    #[test]
+    fn map_linear_shifts_down_unsigned() {
+        // the 2023 day 5 sample's seed-to-soil map
+        let pieces = [
+            (Interval::from_len(98u64, 2), 50),
+            (Interval::from_len(50, 48), 52),
+        ];
+        let seeds = IntervalSet::from(Interval::from_len(79u64, 14));
+        assert_eq!(
+            seeds.map_linear(&pieces),
+            IntervalSet::from(Interval::from_len(81, 14))
+        );
+        // 98 and 99 move down to 50 and 51, 100 and on stay put
+        let s = IntervalSet::from(Interval::new(96u64, 102));
+        let mapped = s.map_linear(&pieces);
+        let expected = [
+            Interval::new(50, 52),
+            Interval::new(98, 100),
+            Interval::new(100, 102),
+        ];
+        assert_eq!(mapped, expected.into_iter().collect());
+        assert_eq!(mapped.len(), s.len());
+        // down to zero
+        let s = IntervalSet::from(Interval::new(10u64, 15));
+        assert_eq!(
+            s.map_linear(&[(Interval::new(10, 12), 0)]),
+            [Interval::new(0, 2), Interval::new(12, 15)]
+                .into_iter()
+                .collect()
+        );
+    }
+
+    #[test]
+    fn map_linear_first_piece_wins() {
+        let s = IntervalSet::from(Interval::new(0u32, 10));
+        // both pieces cover 4..6, the first one moves it
+        let pieces = [(Interval::new(2, 6), 100), (Interval::new(4, 8), 200)];
+        let expected = set(&[(0, 2), (100, 104), (202, 204), (8, 10)]);
+        assert_eq!(s.map_linear(&pieces), expected);
+        let pieces = [(Interval::new(4, 8), 200), (Interval::new(2, 6), 100)];
+        let expected = set(&[(0, 2), (100, 102), (200, 204), (8, 10)]);
+        assert_eq!(s.map_linear(&pieces), expected);
+    }
+}
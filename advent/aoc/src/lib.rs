@@ -0,0 +1,39 @@
+//! Shared plumbing for the Advent of Code solutions in this workspace.
+//!
+//! Every puzzle implements [`Solution`] and each year crate exports a table of
+//! [`Day`] entries which the `aoc` runner dispatches on. Inputs are looked up
+//! in the year's `input` directory, see [`input`], and checked against the
+//! recorded ones in [`answers`].
+
+pub mod aabox;
+pub mod answers;
+pub mod bench;
+pub mod bigint;
+pub mod cycle;
+pub mod geom;
+pub mod grid;
+pub mod guesses;
+pub mod http;
+pub mod image;
+pub mod input;
+pub mod interval;
+pub mod linalg;
+pub mod math;
+pub mod mock;
+pub mod ocr;
+pub mod online;
+pub mod parse;
+pub mod polygon;
+pub mod rational;
+pub mod report;
+pub mod search;
+mod solution;
+pub mod space;
+pub mod viz;
+
+pub use geom::{Dir, Point};
+pub use grid::Grid;
+pub use input::Variant;
+pub use parse::{ParseError, ParseResult};
+pub use report::Value;
+pub use solution::{Day, Part, Solution, Year};
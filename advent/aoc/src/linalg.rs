@@ -0,0 +1,139 @@
+//! Exact Gaussian elimination over [`Rational`]s.
+
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use crate::rational::Rational;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// The equations contradict each other, there is no solution.
+    Inconsistent,
+    /// The equations don't pin down every unknown: `free` of them can take any
+    /// value.
+    Underdetermined { free: usize },
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::Inconsistent => write!(f, "the system has no solution"),
+            SolveError::Underdetermined { free } => write!(
+                f,
+                "the system has infinitely many solutions ({} free unknowns)",
+                free
+            ),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+/// Solves a linear system given as an augmented matrix: every row holds the
+/// coefficients of the unknowns followed by the right hand side. There may be
+/// more equations than unknowns as long as they agree with each other; a
+/// singular square system is either inconsistent or underdetermined.
+pub fn solve(matrix: &[Vec<Rational>]) -> Result<Vec<Rational>, SolveError> {
+    let mut m = matrix.to_vec();
+    let unknowns = m.first().map_or(0, |row| row.len() - 1);
+    assert!(
+        m.iter().all(|row| row.len() == unknowns + 1),
+        "ragged augmented matrix"
+    );
+
+    // reduced row echelon form, the first `rank` rows have a pivot
+    let mut rank = 0;
+    for col in 0..unknowns {
+        let row = rank;
+        let Some(nonzero) = (row..m.len()).find(|&i| !m[i][col].is_zero()) else {
+            continue;
+        };
+        m.swap(row, nonzero);
+        let scale = m[row][col].recip();
+        for x in &mut m[row][col..] {
+            *x = &*x * &scale;
+        }
+        for i in 0..m.len() {
+            if i == row || m[i][col].is_zero() {
+                continue;
+            }
+            let factor = m[i][col].clone();
+            let pivot_row = m[row].clone();
+            for (x, p) in m[i][col..].iter_mut().zip(&pivot_row[col..]) {
+                *x = &*x - &(&factor * p);
+            }
+        }
+        rank += 1;
+    }
+
+    // every row left over reads 0 == rhs
+    if m[rank..].iter().any(|row| !row[unknowns].is_zero()) {
+        return Err(SolveError::Inconsistent);
+    }
+    if rank < unknowns {
+        return Err(SolveError::Underdetermined {
+            free: unknowns - rank,
+        });
+    }
+    Ok(m.into_iter()
+        .take(unknowns)
+        .map(|row| row[unknowns].clone())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix(rows: &[&[i64]]) -> Vec<Vec<Rational>> {
+        rows.iter()
+            .map(|row| row.iter().map(|&x| Rational::from(x)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn unique_solution() {
+        // x + y + z = 6, 2y + 5z = -4, 2x + 5y - z = 27
+        let m = matrix(&[&[1, 1, 1, 6], &[0, 2, 5, -4], &[2, 5, -1, 27]]);
+        let xs = [5i64, 3, -2].map(Rational::from);
+        assert_eq!(solve(&m), Ok(xs.to_vec()));
+        // a zero pivot in the first row needs a swap, and the answer isn't
+        // whole
+        let m = matrix(&[&[0, 2, 1], &[3, 1, 2]]);
+        assert_eq!(
+            solve(&m),
+            Ok(vec![Rational::new(1i64, 2i64), Rational::new(1i64, 2i64)])
+        );
+    }
+
+    #[test]
+    fn singular_systems() {
+        // the second row is twice the first, and so is its right hand side
+        let m = matrix(&[&[1, 2, 3], &[2, 4, 6]]);
+        assert_eq!(solve(&m), Err(SolveError::Underdetermined { free: 1 }));
+        // or isn't
+        let m = matrix(&[&[1, 2, 3], &[2, 4, 7]]);
+        assert_eq!(solve(&m), Err(SolveError::Inconsistent));
+        let m = matrix(&[&[0, 0, 0, 0], &[0, 0, 0, 0]]);
+        assert_eq!(solve(&m), Err(SolveError::Underdetermined { free: 3 }));
+        // a contradiction wins over free unknowns
+        let m = matrix(&[&[1, 1, 1, 1], &[1, 1, 1, 2]]);
+        assert_eq!(solve(&m), Err(SolveError::Inconsistent));
+    }
+
+    #[test]
+    fn more_equations_than_unknowns() {
+        // three lines through (2, -1)
+        let m = matrix(&[&[1, 1, 1], &[1, -1, 3], &[3, 2, 4]]);
+        assert_eq!(
+            solve(&m),
+            Ok(vec![Rational::from(2i64), Rational::from(-1i64)])
+        );
+        // and one that misses it
+        let m = matrix(&[&[1, 1, 1], &[1, -1, 3], &[3, 2, 5]]);
+        assert_eq!(solve(&m), Err(SolveError::Inconsistent));
+        // fewer equations than unknowns can't pin them all down
+        let m = matrix(&[&[1, 0, 2, 3]]);
+        assert_eq!(solve(&m), Err(SolveError::Underdetermined { free: 2 }));
+    }
+}
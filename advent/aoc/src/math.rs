@@ -0,0 +1,148 @@
+//! Number theory for cycle alignment puzzles: when do several things that
+//! repeat with different periods and offsets line up?
+
+/// The greatest common divisor, `gcd(0, 0) == 0`.
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, or `None` if it doesn't fit a `u64`.
+/// `lcm(a, 0) == 0`.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// The least common multiple of all of `nums`, 1 if there are none.
+pub fn lcm_all(nums: impl IntoIterator<Item = u64>) -> Option<u64> {
+    nums.into_iter().try_fold(1, lcm)
+}
+
+/// Extended Euclid: `(g, x, y)` such that `a * x + b * y == g` with `g` the
+/// non-negative gcd of `a` and `b`.
+pub fn ext_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = ext_gcd_wide(a as i128, b as i128);
+    // |x| <= |b / g| and |y| <= |a / g| so these always fit, except for
+    // gcd(i64::MIN, 0) which is 2^63
+    (g as i64, x as i64, y as i64)
+}
+
+fn ext_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    if r0 < 0 {
+        (-r0, -x0, -y0)
+    } else {
+        (r0, x0, y0)
+    }
+}
+
+/// The inverse of `a` modulo `m`, in `0..m`, if `a` and `m` are coprime.
+pub fn mod_inv(a: i64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+    let (g, x, _) = ext_gcd_wide(a as i128, m as i128);
+    (g == 1).then(|| x.rem_euclid(m as i128) as i64)
+}
+
+/// Chinese remainder theorem: solves `x ≡ r (mod m)` for every `(r, m)` of
+/// `congruences` at once, giving `(x, l)` with `l` the lcm of the moduli and
+/// `x` the smallest non-negative solution, so all solutions are `x + k * l`.
+///
+/// The moduli don't have to be coprime, nor the residues reduced. Returns
+/// `None` if the congruences contradict each other, a modulus isn't positive
+/// or `l` doesn't fit an `i64`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let (mut x, mut l) = (0i128, 1i128);
+    for (r, m) in congruences {
+        if m <= 0 {
+            return None;
+        }
+        let (r, m) = ((r as i128).rem_euclid(m as i128), m as i128);
+        // x + l * k ≡ r (mod m), i.e. l * k ≡ r - x (mod m)
+        let (g, inv, _) = ext_gcd_wide(l, m);
+        if (r - x) % g != 0 {
+            return None;
+        }
+        let m_g = m / g;
+        let k = ((r - x) / g % m_g * inv % m_g).rem_euclid(m_g);
+        x += l * k;
+        l *= m_g;
+        if l > i64::MAX as i128 {
+            return None;
+        }
+    }
+    Some((x as i64, l as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(5, 0), Some(0));
+        assert_eq!(lcm(u64::MAX, u64::MAX), Some(u64::MAX));
+        assert_eq!(lcm(u64::MAX, 2), None);
+        assert_eq!(lcm_all([2, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all([]), Some(1));
+        assert_eq!(lcm_all([1 << 40, 3 << 30, 5 << 30]), Some(15 << 40));
+        assert_eq!(lcm_all([1 << 40, 3, 5, 7, 11, 13, 17, 19, 23]), None);
+    }
+
+    #[test]
+    fn ext_gcd_and_inverses() {
+        for (a, b) in [(240, 46), (-240, 46), (240, -46), (0, 5), (7, 0), (-7, 0)] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(g, gcd(a.unsigned_abs(), b.unsigned_abs()) as i64);
+            assert_eq!(a * x + b * y, g, "{} {}", a, b);
+        }
+        assert_eq!(mod_inv(3, 7), Some(5));
+        // negative numbers are taken modulo m first
+        assert_eq!(mod_inv(-3, 7), Some(2));
+        assert_eq!(mod_inv(6, 9), None);
+        assert_eq!(mod_inv(3, 0), None);
+        assert_eq!(mod_inv(3, -7), None);
+    }
+
+    #[test]
+    fn crt_coprime_and_not() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([]), Some((0, 1)));
+        // the moduli share a factor but the residues agree on it
+        assert_eq!(crt([(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt([(0, 6), (0, 9), (0, 15)]), Some((0, 90)));
+        // and here they don't
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(1, 3), (2, 3)]), None);
+    }
+
+    #[test]
+    fn crt_residues_and_moduli_out_of_range() {
+        // residues needn't be reduced, nor non-negative
+        assert_eq!(crt([(-1, 3), (-1, 5)]), Some((14, 15)));
+        assert_eq!(crt([(17, 5), (-10, 4)]), Some((2, 20)));
+        assert_eq!(crt([(1, 0)]), None);
+        assert_eq!(crt([(1, -3)]), None);
+        // the lcm of these is past i64::MAX
+        assert_eq!(crt([(0, 1 << 62), (0, 3)]), None);
+        assert_eq!(crt([(1, 1 << 61), (1, 3)]), Some((1, 3 << 61)));
+    }
+}
@@ -0,0 +1,324 @@
+//! A local stand-in for adventofcode.com, so that [`crate::online`] can be
+//! exercised without a network or an account.
+//!
+//! The [`Server`] listens on a free port of 127.0.0.1 in a thread of its own
+//! and answers the way the real site does, as far as `aoc` cares: plain
+//! text errors for a missing session or a locked puzzle and the input as is
+//! otherwise. Submitted answers get the site's verdicts, including the wait
+//! imposed after a wrong one. It stops when dropped.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::Part;
+
+/// What the server knows, shared with its thread.
+#[derive(Debug, Default)]
+struct State {
+    session: String,
+    inputs: HashMap<(u16, u8), String>,
+    answers: HashMap<(u16, u8, Part), String>,
+    solved: HashSet<(u16, u8, Part)>,
+    penalty: Duration,
+    // no answers are judged before then
+    locked_until: Option<Instant>,
+    // "GET /2023/day/1/input" and so on, in the order they came
+    log: Vec<String>,
+}
+
+pub struct Server {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Server {
+    /// Starts a server that lets in whoever sends the cookie
+    /// `session=<session>`.
+    pub fn start(session: &str) -> io::Result<Server> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State {
+            session: session.to_string(),
+            penalty: Duration::from_secs(60),
+            ..State::default()
+        }));
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = {
+            let (state, stop) = (state.clone(), stop.clone());
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    // a client that goes away only loses its own response
+                    let _ = stream.and_then(|stream| serve(stream, &state));
+                }
+            })
+        };
+        Ok(Server {
+            addr,
+            state,
+            stop,
+            thread: Some(thread),
+        })
+    }
+
+    /// The base URL to hand to [`crate::online::Client::new`].
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Makes `input` the input of `year` `day`. Days without one are
+    /// treated as not open yet.
+    pub fn add_input(&self, year: u16, day: u8, input: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.inputs.insert((year, day), input.to_string());
+    }
+
+    /// Makes `answer` the right one for `part` of `year` `day`.
+    pub fn add_answer(&self, year: u16, day: u8, part: Part, answer: &str) {
+        let mut state = self.state.lock().unwrap();
+        state.answers.insert((year, day, part), answer.to_string());
+    }
+
+    /// How long a wrong answer locks out further ones, a minute by default.
+    pub fn set_penalty(&self, penalty: Duration) {
+        self.state.lock().unwrap().penalty = penalty;
+    }
+
+    /// The method and path of every request so far.
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().log.clone()
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // wakes the thread up from accept
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// A request as the server sees it.
+struct Request {
+    method: String,
+    path: String,
+    cookie: Option<String>,
+    form: HashMap<String, String>,
+}
+
+fn serve(stream: TcpStream, state: &Mutex<State>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut words = line.split_whitespace();
+    let (Some(method), Some(path)) = (words.next(), words.next()) else {
+        return Ok(());
+    };
+    let mut request = Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        cookie: None,
+        form: HashMap::new(),
+    };
+    let mut length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "cookie" => request.cookie = value.strip_prefix("session=").map(str::to_string),
+            "content-length" => length = value.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    for pair in String::from_utf8_lossy(&body).split('&') {
+        if let Some((name, value)) = pair.split_once('=') {
+            request.form.insert(url_decode(name), url_decode(value));
+        }
+    }
+
+    let (status, text) = respond(&request, &mut state.lock().unwrap());
+    let content_type = if text.starts_with('<') {
+        "text/html"
+    } else {
+        "text/plain"
+    };
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        _ => "Error",
+    };
+    let mut stream = reader.into_inner();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        reason,
+        content_type,
+        text.len(),
+        text
+    )?;
+    stream.flush()
+}
+
+fn respond(request: &Request, state: &mut State) -> (u16, String) {
+    state
+        .log
+        .push(format!("{} {}", request.method, request.path));
+    let route = request
+        .path
+        .trim_start_matches('/')
+        .split('/')
+        .collect::<Vec<_>>();
+    let (year, day) = match route[..] {
+        [year, "day", day, _] => match (year.parse::<u16>(), day.parse::<u8>()) {
+            (Ok(year), Ok(day)) => (year, day),
+            _ => return (404, "404 Not Found".to_string()),
+        },
+        _ => return (404, "404 Not Found".to_string()),
+    };
+    let logged_in = request.cookie.as_deref() == Some(state.session.as_str());
+
+    match (request.method.as_str(), route[3]) {
+        ("GET", "input") if !logged_in => (
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".to_string(),
+        ),
+        ("GET", "input") => match state.inputs.get(&(year, day)) {
+            Some(input) => (200, input.clone()),
+            None => (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks! \
+                 The calendar countdown is synchronized with the server time; \
+                 the link will be enabled on the calendar the instant this puzzle \
+                 becomes available.\n"
+                    .to_string(),
+            ),
+        },
+        ("POST", "answer") if !logged_in => {
+            (400, "Please log in to submit an answer.\n".to_string())
+        }
+        ("POST", "answer") => (200, page(&judge(request, state, year, day))),
+        _ => (404, "404 Not Found".to_string()),
+    }
+}
+
+// The text of the page the site answers a submission with.
+fn judge(request: &Request, state: &mut State, year: u16, day: u8) -> String {
+    let part = request
+        .form
+        .get("level")
+        .and_then(|l| l.parse::<Part>().ok());
+    let answer = request.form.get("answer").map_or("", |a| a.trim());
+    let wrong_level = "You don't seem to be solving the right level.  Did you already \
+                       complete it?"
+        .to_string();
+    let Some(part) = part else {
+        return wrong_level;
+    };
+    if state.solved.contains(&(year, day, part))
+        || (part == Part::Two && !state.solved.contains(&(year, day, Part::One)))
+    {
+        return wrong_level;
+    }
+
+    let now = Instant::now();
+    if let Some(left) = state
+        .locked_until
+        .and_then(|t| t.checked_duration_since(now))
+    {
+        let secs = left.as_secs_f64().ceil() as u64;
+        let left = match secs {
+            0..60 => format!("{}s", secs),
+            _ => format!("{}m {}s", secs / 60, secs % 60),
+        };
+        return format!(
+            "You gave an answer too recently; you have to wait after submitting an \
+             answer before trying again.  You have {} left to wait.",
+            left
+        );
+    }
+    let Some(right) = state.answers.get(&(year, day, part)) else {
+        return wrong_level;
+    };
+    if answer == right {
+        state.solved.insert((year, day, part));
+        return "That's the right answer!  You are one gold star closer to saving \
+                Christmas."
+            .to_string();
+    }
+
+    state.locked_until = Some(now + state.penalty);
+    let hint = match (answer.parse::<i128>(), right.parse::<i128>()) {
+        (Ok(a), Ok(r)) if a > r => "; your answer is too high",
+        (Ok(a), Ok(r)) if a < r => "; your answer is too low",
+        _ => "",
+    };
+    let secs = state.penalty.as_secs();
+    let wait = match secs {
+        60 => "one minute".to_string(),
+        _ if secs.is_multiple_of(60) => format!("{} minutes", secs / 60),
+        _ => format!("{} seconds", secs),
+    };
+    format!(
+        "That's not the right answer{}.  If you're stuck, make sure you're using \
+         the full input data.  Please wait {} before trying again.",
+        hint, wait
+    )
+}
+
+// Dresses `text` up like the site's pages, where it's the only <article>.
+fn page(text: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en-us\">\n<head><title>Day - Advent of Code</title>\
+         </head>\n<body>\n<main>\n<article><p>{}</p></article>\n</main>\n</body>\n</html>\n",
+        text
+    )
+}
+
+// Undoes the %XX and + escapes of a form field.
+fn url_decode(s: &str) -> String {
+    let mut bytes = vec![];
+    let mut rest = s.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        rest = tail;
+        match b {
+            b'+' => bytes.push(b' '),
+            b'%' if rest.len() >= 2 => {
+                match std::str::from_utf8(&rest[..2])
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                {
+                    Some(b) => {
+                        bytes.push(b);
+                        rest = &rest[2..];
+                    }
+                    None => bytes.push(b'%'),
+                }
+            }
+            b => bytes.push(b),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
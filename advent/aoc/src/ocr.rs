@@ -0,0 +1,244 @@
+//! Reading the capital letters some puzzles draw instead of printing an
+//! answer.
+//!
+//! There are two fonts, one 6 pixels tall with letters mostly 4 wide and one
+//! 10 tall with letters 6 wide, and neither has every letter. Letters are
+//! told apart by the blank columns between them, so the spacing doesn't
+//! matter and a letter like `I`, narrower than its cell, still matches.
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{self, Display};
+
+use crate::{Grid, Point};
+
+const SMALL: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[rustfmt::skip]
+const LARGE: &[(char, [&str; 10])] = &[
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#",
+           "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.",
+           "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....",
+           "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.",
+           "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.",
+           "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....",
+           "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######",
+           "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.",
+           "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....",
+           "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....",
+           "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#",
+           "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.",
+           "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.",
+           "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..",
+           "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..",
+           "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OcrError {
+    /// The lit pixels span `0` rows, or some other height no font has.
+    Height(usize),
+    /// Glyphs matching no letter of the font, each with the column it starts
+    /// at and its bitmap drawn in `#` and `.`.
+    Unrecognized(Vec<(usize, String)>),
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OcrError::Height(h) => write!(f, "letters {} pixels tall, expected 6 or 10", h),
+            OcrError::Unrecognized(glyphs) => {
+                for (i, (col, bitmap)) in glyphs.iter().enumerate() {
+                    if i > 0 {
+                        writeln!(f)?;
+                    }
+                    write!(f, "unrecognized glyph at column {}:\n{}", col, bitmap)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error for OcrError {}
+
+/// The letters drawn by the `true` cells of `grid`, left to right. The font
+/// is picked by the height of what's drawn; blank rows and columns around it
+/// are ignored.
+pub fn read(grid: &Grid<bool>) -> Result<String, OcrError> {
+    let lit_rows = (0..grid.rows())
+        .filter(|&r| grid.row(r).contains(&true))
+        .collect::<Vec<_>>();
+    let (top, height) = match (lit_rows.first(), lit_rows.last()) {
+        (Some(&top), Some(&bottom)) => (top, bottom - top + 1),
+        _ => (0, 0),
+    };
+    let find: fn(&[String]) -> Option<char> = match height {
+        6 => |glyph| lookup(SMALL, glyph),
+        10 => |glyph| lookup(LARGE, glyph),
+        h => return Err(OcrError::Height(h)),
+    };
+    let rows = (top..top + height).map(|r| grid.row(r)).collect::<Vec<_>>();
+    let blank = |c: usize| rows.iter().all(|row| !row[c]);
+
+    let mut letters = String::new();
+    let mut unrecognized = vec![];
+    let mut c = 0;
+    while c < grid.cols() {
+        if blank(c) {
+            c += 1;
+            continue;
+        }
+        let start = c;
+        while c < grid.cols() && !blank(c) {
+            c += 1;
+        }
+        let glyph = rows
+            .iter()
+            .map(|row| row[start..c].iter().map(|&on| if on { '#' } else { '.' }))
+            .map(String::from_iter)
+            .collect::<Vec<_>>();
+        match find(&glyph) {
+            Some(letter) => letters.push(letter),
+            None => unrecognized.push((start, glyph.join("\n"))),
+        }
+    }
+    if !unrecognized.is_empty() {
+        return Err(OcrError::Unrecognized(unrecognized));
+    }
+    Ok(letters)
+}
+
+/// Like [`read`], for the letters drawn by `points` with `y` going down.
+/// Columns are counted from the leftmost point.
+pub fn read_points(points: impl IntoIterator<Item = Point>) -> Result<String, OcrError> {
+    let points = points.into_iter().collect::<HashSet<_>>();
+    let min_x = points.iter().map(|p| p.x).min().unwrap_or(0);
+    let min_y = points.iter().map(|p| p.y).min().unwrap_or(0);
+    let cols = points.iter().map(|p| p.x - min_x + 1).max().unwrap_or(0);
+    let rows = points.iter().map(|p| p.y - min_y + 1).max().unwrap_or(0);
+    let grid = Grid::from_fn(rows as usize, cols as usize, |(r, c)| {
+        points.contains(&Point::new(min_x + c as i64, min_y + r as i64))
+    });
+    read(&grid)
+}
+
+// The letter of `font` drawn by `glyph`, whose blank columns on either side
+// are already cut off. Those of the font's letters are cut off here.
+fn lookup<const H: usize>(font: &[(char, [&str; H])], glyph: &[String]) -> Option<char> {
+    font.iter().find_map(|(letter, rows)| {
+        let width = rows[0].len();
+        let lit = |c: usize| rows.iter().any(|row| row.as_bytes()[c] == b'#');
+        let start = (0..width).find(|&c| lit(c))?;
+        let end = (0..width).rfind(|&c| lit(c))? + 1;
+        rows.iter()
+            .zip(glyph)
+            .all(|(row, g)| row[start..end] == *g)
+            .then_some(*letter)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Grid<bool> {
+        Grid::parse(&rows.join("\n"), |c| Ok::<_, String>(c == '#')).unwrap()
+    }
+
+    #[test]
+    fn reads_both_fonts() {
+        // letters of the small font, an I with its narrow cell and padding
+        let small = grid(&[
+            "..........................",
+            "..#..#.###..####...###....",
+            "..#..#..#......#...#..#...",
+            "..####..#.....#....###....",
+            "..#..#..#....#.....#..#...",
+            "..#..#..#...#......#..#...",
+            "..#..#.###..####...###....",
+        ]);
+        assert_eq!(read(&small), Ok("HIZB".to_string()));
+
+        let large = LARGE
+            .iter()
+            .filter(|(letter, _)| "XN".contains(*letter))
+            .map(|(_, rows)| rows)
+            .collect::<Vec<_>>();
+        let rows = (0..10)
+            .map(|r| format!("{}..{}", large[0][r], large[1][r]))
+            .collect::<Vec<_>>();
+        let rows = rows.iter().map(String::as_str).collect::<Vec<_>>();
+        assert_eq!(read(&grid(&rows)), Ok("NX".to_string()));
+    }
+
+    #[test]
+    fn unrecognized_glyphs_come_with_their_bitmap() {
+        let drawn = grid(&[
+            "#..#..#.#..####",
+            "#..#..#.#.....#",
+            "####..###....#.",
+            "#..#....#...#..",
+            "#..#....#..#...",
+            "#..#....#..####",
+        ]);
+        let bitmap = "#.#\n#.#\n###\n..#\n..#\n..#".to_string();
+        assert_eq!(read(&drawn), Err(OcrError::Unrecognized(vec![(6, bitmap)])));
+        let message = read(&drawn).unwrap_err().to_string();
+        assert!(
+            message.starts_with("unrecognized glyph at column 6:\n#.#\n"),
+            "{}",
+            message
+        );
+
+        // with points the columns count from the leftmost one
+        let points = drawn
+            .iter()
+            .filter(|(_, &on)| on)
+            .map(|((r, c), _)| Point::new(c as i64 + 100, r as i64 - 50));
+        let Err(OcrError::Unrecognized(glyphs)) = read_points(points) else {
+            panic!("the 4 is no letter");
+        };
+        assert_eq!(glyphs.iter().map(|g| g.0).collect::<Vec<_>>(), [6]);
+    }
+
+    #[test]
+    fn heights_without_a_font() {
+        assert_eq!(read(&grid(&["...", "..."])), Err(OcrError::Height(0)));
+        assert_eq!(read(&grid(&["#", "#", "#"])), Err(OcrError::Height(3)));
+        assert_eq!(read_points([]), Err(OcrError::Height(0)));
+    }
+}
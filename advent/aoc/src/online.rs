@@ -0,0 +1,398 @@
+//! Talking to adventofcode.com, for `aoc fetch` and `aoc submit`.
+//!
+//! Requests are made as the user whose session cookie the [`Client`] holds
+//! and are throttled, so that a loop over a whole year can't hammer the
+//! site. Inputs land in the year's `input` directory under the names of
+//! [`crate::input`] and are never downloaded again once there. Answers are
+//! held back while the site is still making the user wait after a wrong
+//! one, see [`Client::submit`].
+
+use std::env;
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::guesses::Outcome;
+use crate::http::{self, Response};
+use crate::input;
+use crate::{Part, Variant};
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+/// The least time between two requests of the same user.
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = concat!("aoc-runner/", env!("CARGO_PKG_VERSION"), " (std-only CLI)");
+
+#[derive(Debug)]
+pub enum OnlineError {
+    /// No session cookie in `AOC_SESSION` or the session file.
+    NoSession(PathBuf),
+    /// The site didn't accept the session cookie.
+    LoggedOut,
+    /// The puzzle opens at the given time.
+    Locked(SystemTime),
+    /// A response the site isn't known to give.
+    Status(u16, String),
+    Io(io::Error),
+}
+
+impl Display for OnlineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OnlineError::NoSession(path) => write!(
+                f,
+                "no session cookie, set AOC_SESSION or put it in {}",
+                path.display()
+            ),
+            OnlineError::LoggedOut => write!(f, "the session cookie was rejected, log in again"),
+            OnlineError::Locked(at) => match at.duration_since(SystemTime::now()) {
+                Ok(left) => {
+                    let mins = left.as_secs().div_ceil(60);
+                    write!(
+                        f,
+                        "the puzzle isn't open yet, wait {}h{:02}m",
+                        mins / 60,
+                        mins % 60
+                    )
+                }
+                // the site's clock is behind ours
+                Err(_) => write!(f, "the puzzle isn't open yet, try again shortly"),
+            },
+            OnlineError::Status(status, body) => {
+                write!(f, "unexpected response {}: {}", status, body.trim())
+            }
+            OnlineError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for OnlineError {}
+
+impl From<io::Error> for OnlineError {
+    fn from(e: io::Error) -> Self {
+        OnlineError::Io(e)
+    }
+}
+
+/// A logged in user of the site at `base_url`.
+#[derive(Clone, Debug)]
+pub struct Client {
+    base_url: String,
+    session: String,
+    interval: Duration,
+    // holds the times throttling goes by, shared by every run of the CLI
+    state_dir: Option<PathBuf>,
+    last: Option<SystemTime>,
+    next_answer: Option<SystemTime>,
+}
+
+impl Client {
+    /// A client for the site at `base_url`, throttled in-process only.
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            interval: MIN_INTERVAL,
+            state_dir: None,
+            last: None,
+            next_answer: None,
+        }
+    }
+
+    /// The client `aoc` uses: the session comes from `AOC_SESSION` or
+    /// [`session_file`], the site from `AOC_URL` or [`DEFAULT_URL`], and
+    /// the throttle is kept in [`cache_dir`].
+    pub fn from_env() -> Result<Client, OnlineError> {
+        let session = match env::var("AOC_SESSION") {
+            Ok(session) if !session.trim().is_empty() => session,
+            _ => {
+                let path = session_file();
+                fs::read_to_string(&path)
+                    .ok()
+                    .filter(|s| !s.trim().is_empty())
+                    .ok_or(OnlineError::NoSession(path))?
+            }
+        };
+        let url = env::var("AOC_URL").unwrap_or_else(|_| DEFAULT_URL.to_string());
+        Ok(Client::new(&url, &session).with_throttle(MIN_INTERVAL, Some(cache_dir())))
+    }
+
+    /// Waits `interval` between requests. Given a `state_dir`, the time of
+    /// the last request and when the next answer may be submitted are kept
+    /// in files there, so that separate processes wait for each other.
+    pub fn with_throttle(mut self, interval: Duration, state_dir: Option<PathBuf>) -> Client {
+        self.interval = interval;
+        self.state_dir = state_dir;
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Downloads the input of `year` `day`.
+    pub fn input(&mut self, year: u16, day: u8) -> Result<String, OnlineError> {
+        let unlock = unlock_time(year, day);
+        if SystemTime::now() < unlock {
+            return Err(OnlineError::Locked(unlock));
+        }
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let response = self.request(|headers| http::get(&url, headers))?;
+        match response.status {
+            200 => Ok(response.body),
+            // the site answers both with a plain text plea and no redirect
+            400 | 500 if response.body.contains("log in") => Err(OnlineError::LoggedOut),
+            404 if response.body.contains("before it unlocks") => Err(OnlineError::Locked(unlock)),
+            status => Err(OnlineError::Status(status, response.body)),
+        }
+    }
+
+    /// Submits `answer` to `part` of `year` `day`. Nothing is sent while the
+    /// site would still turn any answer away for coming too soon after a
+    /// wrong one; the wait left is returned instead.
+    pub fn submit(
+        &mut self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+    ) -> Result<Verdict, OnlineError> {
+        let next = self.stamp("next-answer").max(self.next_answer);
+        if let Some(left) = next.and_then(|t| t.duration_since(SystemTime::now()).ok()) {
+            return Ok(Verdict::Wait(left));
+        }
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let level = part.to_string();
+        let form = [("level", level.as_str()), ("answer", answer)];
+        let response = self.request(|headers| http::post_form(&url, headers, &form))?;
+        let verdict = match response.status {
+            200 => parse_verdict(&response.body)
+                .ok_or_else(|| OnlineError::Status(200, article_text(&response.body)))?,
+            400 | 500 if response.body.contains("log in") => return Err(OnlineError::LoggedOut),
+            status => return Err(OnlineError::Status(status, response.body)),
+        };
+
+        let wait = match verdict {
+            Verdict::Judged(_, wait) | Verdict::Wait(wait) => wait,
+            Verdict::WrongLevel => Duration::ZERO,
+        };
+        let next = SystemTime::now() + wait;
+        self.next_answer = Some(next);
+        if let Some(dir) = &self.state_dir {
+            write_stamp(&dir.join("next-answer"), next)?;
+        }
+        Ok(verdict)
+    }
+
+    // Sends a request with the session and user agent headers once the
+    // throttle allows.
+    fn request(
+        &mut self,
+        send: impl FnOnce(&[(&str, &str)]) -> io::Result<Response>,
+    ) -> Result<Response, OnlineError> {
+        let last = self.stamp("last-request").max(self.last);
+        if let Some(wait) =
+            last.and_then(|t| (t + self.interval).duration_since(SystemTime::now()).ok())
+        {
+            thread::sleep(wait);
+        }
+        let cookie = format!("session={}", self.session);
+        let response = send(&[("Cookie", &cookie), ("User-Agent", USER_AGENT)]);
+
+        let now = SystemTime::now();
+        self.last = Some(now);
+        if let Some(dir) = &self.state_dir {
+            write_stamp(&dir.join("last-request"), now)?;
+        }
+        Ok(response?)
+    }
+
+    fn stamp(&self, name: &str) -> Option<SystemTime> {
+        read_stamp(&self.state_dir.as_ref()?.join(name))
+    }
+}
+
+/// What the site said to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The answer was judged, and no other may be submitted for the given
+    /// time.
+    Judged(Outcome, Duration),
+    /// Too soon after the last wrong answer, with the time left to wait.
+    Wait(Duration),
+    /// The part is solved already, or is part 2 of an unsolved day.
+    WrongLevel,
+}
+
+/// Reads the verdict out of the page the site answers a submission with,
+/// `None` if it doesn't look like one.
+pub fn parse_verdict(html: &str) -> Option<Verdict> {
+    let text = article_text(html);
+    if text.contains("That's the right answer") {
+        Some(Verdict::Judged(Outcome::Right, Duration::ZERO))
+    } else if text.contains("That's not the right answer") {
+        let outcome = if text.contains("too high") {
+            Outcome::TooHigh
+        } else if text.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        };
+        // "Please wait one minute before trying again", growing with every
+        // wrong answer
+        let wait = text
+            .split_once("lease wait ")
+            .and_then(|(_, rest)| {
+                let mut words = rest.split_whitespace();
+                let n = match words.next()? {
+                    "one" => 1,
+                    n => n.parse().ok()?,
+                };
+                let unit = match words.next()?.trim_end_matches('s') {
+                    "second" => 1,
+                    "minute" => 60,
+                    "hour" => 3600,
+                    _ => return None,
+                };
+                Some(Duration::from_secs(n * unit))
+            })
+            .unwrap_or(Duration::from_secs(60));
+        Some(Verdict::Judged(outcome, wait))
+    } else if text.contains("You gave an answer too recently") {
+        // "You have 1m 5s left to wait."
+        let left = text
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left"))
+            .map_or(0, |(left, _)| {
+                left.split_whitespace()
+                    .filter_map(|part| {
+                        let (n, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+                        let unit = match unit {
+                            "s" => 1,
+                            "m" => 60,
+                            "h" => 3600,
+                            _ => return None,
+                        };
+                        Some(n.parse::<u64>().ok()? * unit)
+                    })
+                    .sum()
+            });
+        Some(Verdict::Wait(Duration::from_secs(left.max(1))))
+    } else if text.contains("solving the right level") {
+        Some(Verdict::WrongLevel)
+    } else {
+        None
+    }
+}
+
+// The text of the page's <article>, or the whole page if it has none,
+// without tags and with whitespace collapsed.
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| {
+            article.split_once('>').map_or(article, |(_, a)| a)
+        });
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Where an input ended up.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fetched {
+    /// It was there already and nothing was downloaded.
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+/// Makes sure the input of `year` `day` is in `dir`, downloading it only if
+/// it isn't.
+pub fn fetch_input(
+    client: &mut Client,
+    dir: &Path,
+    year: u16,
+    day: u8,
+) -> Result<Fetched, OnlineError> {
+    if let Ok(path) = input::locate(dir, day, Variant::Puzzle) {
+        return Ok(Fetched::Cached(path));
+    }
+    let input = client.input(year, day)?;
+    let path = dir.join(&Variant::Puzzle.file_names(day)[0]);
+    // written aside first, so an interrupted download doesn't look cached
+    let partial = path.with_extension("txt.part");
+    fs::create_dir_all(dir)?;
+    fs::write(&partial, input)?;
+    fs::rename(&partial, &path)?;
+    Ok(Fetched::Downloaded(path))
+}
+
+/// When `year` `day` opens: midnight in the US east, 05:00 UTC.
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let days = days_from_civil(year as i64, 12, day as i64);
+    UNIX_EPOCH + Duration::from_secs((days * 86400 + 5 * 3600).max(0) as u64)
+}
+
+/// `$XDG_CONFIG_HOME/aoc/session`, by default `~/.config/aoc/session`.
+pub fn session_file() -> PathBuf {
+    base_dir("XDG_CONFIG_HOME", ".config").join("aoc/session")
+}
+
+/// `$XDG_CACHE_HOME/aoc`, by default `~/.cache/aoc`.
+pub fn cache_dir() -> PathBuf {
+    base_dir("XDG_CACHE_HOME", ".cache").join("aoc")
+}
+
+/// Where `aoc submit` keeps its [`crate::guesses::History`].
+pub fn history_file() -> PathBuf {
+    cache_dir().join("guesses.txt")
+}
+
+fn base_dir(var: &str, fallback: &str) -> PathBuf {
+    match env::var_os(var) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME").unwrap_or_default()).join(fallback),
+    }
+}
+
+fn read_stamp(path: &Path) -> Option<SystemTime> {
+    let millis = fs::read_to_string(path).ok()?.trim().parse().ok()?;
+    Some(UNIX_EPOCH + Duration::from_millis(millis))
+}
+
+// Rounded up, so that waiting out a stamp never ends early.
+fn write_stamp(path: &Path, time: SystemTime) -> io::Result<()> {
+    let millis = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_micros()
+        .div_ceil(1000);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, millis.to_string())
+}
+
+// Days since 1970-01-01 of a proleptic Gregorian date, after Howard Hinnant.
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((m + 9) % 12) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
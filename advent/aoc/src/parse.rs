@@ -0,0 +1,483 @@
+//! Errors for malformed puzzle input, plus the helpers most parsers need.
+//!
+//! A [`ParseError`] is created from the offending slice of the input, so
+//! parsers deep down don't have to keep track of where they are: the line and
+//! column are worked out by [`ParseError::locate`] once the whole input is at
+//! hand, which [`crate::Day::solve`] does before handing the error out.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based position of `found`, zero until located.
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    /// The offending text, empty if something is missing.
+    pub found: String,
+    addr: usize,
+}
+
+impl ParseError {
+    /// An error pointing at `found`, which must be a slice of the puzzle input
+    /// for the location to be found.
+    pub fn new(found: &str, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            line: 0,
+            column: 0,
+            expected: expected.into(),
+            found: found.to_string(),
+            addr: found.as_ptr() as usize,
+        }
+    }
+
+    /// An error for something missing right after `s`.
+    pub fn after(s: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::new(&s[s.len()..], expected)
+    }
+
+    /// Fills in the line and column if the error points into `input`.
+    pub fn locate(mut self, input: &str) -> ParseError {
+        let start = input.as_ptr() as usize;
+        if self.line == 0 && (start..=start + input.len()).contains(&self.addr) {
+            let before = &input[..self.addr - start];
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+            self.line = before.matches('\n').count() + 1;
+            self.column = before[line_start..].chars().count() + 1;
+        }
+        self
+    }
+
+    /// The offending line of `input` with the error underlined, for a located
+    /// error.
+    pub fn excerpt(&self, input: &str) -> Option<String> {
+        let line = input.lines().nth(self.line.checked_sub(1)?)?;
+        let number = self.line.to_string();
+        let pad = " ".repeat(number.len());
+        Some(format!(
+            "{} |\n{} | {}\n{} | {}{}",
+            pad,
+            number,
+            line,
+            pad,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.found.chars().count().max(1))
+        ))
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.line > 0 {
+            write!(f, "line {}, column {}: ", self.line, self.column)?;
+        }
+        write!(f, "expected {}", self.expected)?;
+        if !self.found.is_empty() {
+            write!(f, ", found {:?}", self.found)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `s` as a number.
+pub fn num<T: FromStr>(s: &str) -> ParseResult<T> {
+    s.parse().map_err(|_| ParseError::new(s, "a number"))
+}
+
+pub fn split_once<'a>(s: &'a str, delim: &str) -> ParseResult<(&'a str, &'a str)> {
+    s.split_once(delim)
+        .ok_or_else(|| ParseError::after(s, format!("{:?}", delim)))
+}
+
+pub fn strip_prefix<'a>(s: &'a str, prefix: &str) -> ParseResult<&'a str> {
+    s.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::new(s.get(..prefix.len()).unwrap_or(s), format!("{:?}", prefix)))
+}
+
+/// The next item of a split of `s`, e.g. the next field of a line.
+pub fn next<'a>(
+    items: &mut impl Iterator<Item = &'a str>,
+    s: &'a str,
+    expected: &str,
+) -> ParseResult<&'a str> {
+    items.next().ok_or_else(|| ParseError::after(s, expected))
+}
+
+/// Maps every char of `line` with `f`, failing on the first one it rejects.
+/// The error of `f` describes what was expected instead, e.g. `"a digit"`.
+pub fn chars<T, E: Display>(
+    line: &str,
+    mut f: impl FnMut(char) -> Result<T, E>,
+) -> ParseResult<Vec<T>> {
+    line.char_indices()
+        .map(|(i, c)| f(c).map_err(|e| ParseError::new(&line[i..i + c.len_utf8()], e.to_string())))
+        .collect()
+}
+
+/// Groups `lines` into the sections of an input separated by blank lines,
+/// e.g. `parse::sections(input.lines())`. Works on borrowed and owned lines
+/// alike. Lines holding only whitespace count as blank, and runs of them,
+/// including any at the start or the end, never make empty sections.
+pub fn sections<I>(lines: I) -> Sections<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    Sections {
+        lines: lines.into_iter(),
+    }
+}
+
+/// The iterator returned by [`sections`], yielding the lines of a section at
+/// a time.
+#[derive(Clone, Debug)]
+pub struct Sections<I> {
+    lines: I,
+}
+
+impl<I> Sections<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    /// Splits the first line off every section, for inputs where it names
+    /// the section, like `--- scanner 0 ---` or `seed-to-soil map:`.
+    pub fn with_headers(self) -> impl Iterator<Item = (I::Item, Vec<I::Item>)> {
+        self.map(|mut section| {
+            let rest = section.split_off(1);
+            (section.pop().unwrap(), rest)
+        })
+    }
+}
+
+impl<I> Iterator for Sections<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut section = vec![];
+        for line in self.lines.by_ref() {
+            if !line.as_ref().trim().is_empty() {
+                section.push(line);
+            } else if !section.is_empty() {
+                break;
+            }
+        }
+        (!section.is_empty()).then_some(section)
+    }
+}
+
+/// Matches `s` against `pattern` and parses the fields in it, e.g.
+/// `scan("x=-5..10", "{}={}..{}")` gives `("x", -5, 10)` as a
+/// `(&str, i32, i32)`.
+///
+/// Every `{}` in the pattern is a field, everything else has to be there as
+/// is, with `{{` and `}}` standing for braces. A field runs up to the first
+/// occurrence of the text following it in the pattern, or to the end of `s`.
+/// The fields come out as a tuple or an array of [`Field`]s and errors point
+/// at the offending part of `s`.
+///
+/// Panics if the pattern is malformed, has two fields next to each other or
+/// doesn't have as many fields as asked for.
+pub fn scan<'a, T: Fields<'a>>(s: &'a str, pattern: &str) -> ParseResult<T> {
+    let literals = pattern_literals(pattern);
+    assert_eq!(
+        literals.len() - 1,
+        T::COUNT,
+        "the pattern {:?} has the wrong number of fields",
+        pattern
+    );
+
+    let mut rest = strip_prefix(s, &literals[0])?;
+    let mut fields = Vec::with_capacity(T::COUNT);
+    for (i, lit) in literals.iter().enumerate().skip(1) {
+        let field;
+        if i == literals.len() - 1 {
+            // the last bit of text has to end the line
+            field = rest
+                .strip_suffix(lit.as_str())
+                .ok_or_else(|| ParseError::after(rest, format!("{:?}", lit)))?;
+            rest = "";
+        } else {
+            assert!(!lit.is_empty(), "adjacent fields in {:?}", pattern);
+            (field, rest) = split_once(rest, lit)?;
+        }
+        fields.push(field);
+    }
+    if !rest.is_empty() {
+        return Err(ParseError::new(rest, "the end of the line"));
+    }
+    T::from_fields(&fields)
+}
+
+// The text between the fields of a pattern, one more than there are fields.
+fn pattern_literals(pattern: &str) -> Vec<String> {
+    let mut literals = vec![String::new()];
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        let c = match (c, chars.clone().next()) {
+            ('{', Some('}')) => {
+                chars.next();
+                literals.push(String::new());
+                continue;
+            }
+            ('{', Some('{')) | ('}', Some('}')) => chars.next().unwrap(),
+            ('{' | '}', _) => panic!("unmatched {:?} in {:?}", c, pattern),
+            _ => c,
+        };
+        literals.last_mut().unwrap().push(c);
+    }
+    literals
+}
+
+/// A value that can be read from a field of a [`scan`] pattern.
+pub trait Field<'a>: Sized {
+    fn from_field(s: &'a str) -> ParseResult<Self>;
+}
+
+/// The text of the field as is.
+impl<'a> Field<'a> for &'a str {
+    fn from_field(s: &'a str) -> ParseResult<&'a str> {
+        Ok(s)
+    }
+}
+
+impl Field<'_> for char {
+    fn from_field(s: &str) -> ParseResult<char> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(ParseError::new(s, "a single character")),
+        }
+    }
+}
+
+// Numbers may be padded with spaces, like in aligned columns.
+macro_rules! number_fields {
+    ($($t:ty),*) => {$(
+        impl Field<'_> for $t {
+            fn from_field(s: &str) -> ParseResult<$t> {
+                num(s.trim())
+            }
+        }
+    )*};
+}
+
+number_fields!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// All the fields of a [`scan`] pattern: a tuple of [`Field`]s or an array of
+/// them.
+pub trait Fields<'a>: Sized {
+    const COUNT: usize;
+
+    fn from_fields(fields: &[&'a str]) -> ParseResult<Self>;
+}
+
+impl<'a, T: Field<'a>, const N: usize> Fields<'a> for [T; N] {
+    const COUNT: usize = N;
+
+    fn from_fields(fields: &[&'a str]) -> ParseResult<[T; N]> {
+        let values = fields
+            .iter()
+            .map(|s| T::from_field(s))
+            .collect::<ParseResult<Vec<_>>>()?;
+        Ok(values.try_into().ok().unwrap())
+    }
+}
+
+macro_rules! tuple_fields {
+    ($($t:ident)+) => {
+        impl<'a, $($t: Field<'a>),+> Fields<'a> for ($($t,)+) {
+            const COUNT: usize = [$(stringify!($t)),+].len();
+
+            fn from_fields(fields: &[&'a str]) -> ParseResult<Self> {
+                let mut fields = fields.iter();
+                Ok(($($t::from_field(fields.next().unwrap())?,)+))
+            }
+        }
+    };
+}
+
+tuple_fields!(A);
+tuple_fields!(A B);
+tuple_fields!(A B C);
+tuple_fields!(A B C D);
+tuple_fields!(A B C D E);
+tuple_fields!(A B C D E F);
+tuple_fields!(A B C D E F G);
+tuple_fields!(A B C D E F G H);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locate_on_the_first_line() {
+        let input = "abc\ndef\n";
+        let e = ParseError::new(&input[1..3], "a digit").locate(input);
+        assert_eq!((e.line, e.column), (1, 2));
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 2: expected a digit, found \"bc\""
+        );
+        assert_eq!(e.excerpt(input).unwrap(), "  |\n1 | abc\n  |  ^^");
+        // columns count chars, not bytes
+        let input = "é=x";
+        let e = ParseError::new(&input[3..], "a number").locate(input);
+        assert_eq!((e.line, e.column), (1, 3));
+    }
+
+    #[test]
+    fn locate_on_the_last_line() {
+        let input = "ab\ncd\nef";
+        let e = ParseError::new(&input[7..], "a digit").locate(input);
+        assert_eq!((e.line, e.column), (3, 2));
+        assert_eq!(e.excerpt(input).unwrap(), "  |\n3 | ef\n  |  ^");
+        // something missing at the very end gets a caret past the text
+        let e = ParseError::after(input, "';'").locate(input);
+        assert_eq!((e.line, e.column), (3, 3));
+        assert_eq!(e.to_string(), "line 3, column 3: expected ';'");
+        assert_eq!(e.excerpt(input).unwrap(), "  |\n3 | ef\n  |   ^");
+        // wider line numbers widen the gutter
+        let input = "x\n".repeat(11);
+        let e = ParseError::new(&input[20..21], "y").locate(&input);
+        assert_eq!(e.excerpt(&input).unwrap(), "   |\n11 | x\n   | ^");
+    }
+
+    #[test]
+    fn locate_outside_the_input() {
+        let input = "abc";
+        let other = String::from("abc");
+        let e = ParseError::new(&other, "a digit").locate(input);
+        assert_eq!((e.line, e.column), (0, 0));
+        assert_eq!(e.to_string(), "expected a digit, found \"abc\"");
+        assert_eq!(e.excerpt(input), None);
+        // once located, an error keeps its place
+        let e = ParseError::new(&input[2..], "a digit").locate(input);
+        assert_eq!(e.clone().locate("zzz"), e);
+    }
+
+    #[test]
+    fn scan_fields() {
+        let x: (&str, i32, i32) = scan("x=-5..10", "{}={}..{}").unwrap();
+        assert_eq!(x, ("x", -5, 10));
+        // everything but the fields is matched as is, regex characters too
+        let node: (&str, &str, &str) = scan("AAA = (BBB, CCC)", "{} = ({}, {})").unwrap();
+        assert_eq!(node, ("AAA", "BBB", "CCC"));
+        let dims: [u32; 3] = scan("2x3x4", "{}x{}x{}").unwrap();
+        assert_eq!(dims, [2, 3, 4]);
+        // a field ends at the first match of what follows it
+        let split: (&str, &str) = scan("a-b-c", "{}-{}").unwrap();
+        assert_eq!(split, ("a", "b-c"));
+        // numbers may be padded, text isn't trimmed
+        let padded: (u8, &str) = scan("  7: x ", "{}:{}").unwrap();
+        assert_eq!(padded, (7, " x "));
+        let (c,): (char,) = scan("move é", "move {}").unwrap();
+        assert_eq!(c, 'é');
+    }
+
+    #[test]
+    fn scan_braces_and_trailing_text() {
+        let (name, n): (&str, u8) = scan("{a} 5", "{{{}}} {}").unwrap();
+        assert_eq!((name, n), ("a", 5));
+        let (n,): (u8,) = scan("}5{", "}}{}{{").unwrap();
+        assert_eq!(n, 5);
+        let (n,): (u8,) = scan("(5)", "({})").unwrap();
+        assert_eq!(n, 5);
+        // the text after the last field has to end the line
+        let e = scan::<(u8,)>("(5", "({})").unwrap_err();
+        assert_eq!((e.expected.as_str(), e.found.as_str()), ("\")\"", ""));
+        assert!(scan::<(u8,)>("(5) ", "({})").is_err());
+    }
+
+    #[test]
+    fn scan_errors_point_into_the_line() {
+        let line = "x=1,y";
+        let e = scan::<(u8, u8)>(line, "x={},{}").unwrap_err().locate(line);
+        assert_eq!(
+            (e.column, e.expected.as_str(), e.found.as_str()),
+            (5, "a number", "y")
+        );
+
+        let e = scan::<(u8,)>(line, "y={}").unwrap_err().locate(line);
+        assert_eq!((e.column, e.found.as_str()), (1, "x="));
+
+        // a missing separator is reported at the end of the line
+        let e = scan::<(u8, u8)>(line, "x={};{}").unwrap_err().locate(line);
+        assert_eq!((e.column, e.expected.as_str()), (6, "\";\""));
+
+        let e = scan::<(char,)>("go ab", "go {}").unwrap_err();
+        assert_eq!(
+            (e.expected.as_str(), e.found.as_str()),
+            ("a single character", "ab")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the wrong number of fields")]
+    fn scan_checks_the_field_count() {
+        let _ = scan::<(u8, u8)>("1", "{}");
+    }
+
+    #[test]
+    #[should_panic(expected = "unmatched '{'")]
+    fn scan_rejects_unmatched_braces() {
+        let _ = scan::<(u8,)>("1 {", "{} {");
+    }
+
+    #[test]
+    #[should_panic(expected = "adjacent fields")]
+    fn scan_rejects_adjacent_fields() {
+        let _ = scan::<(u8, u8)>("12", "{}{}");
+    }
+
+    fn sections_of(input: &str) -> Vec<Vec<&str>> {
+        sections(input.lines()).collect()
+    }
+
+    #[test]
+    fn sections_split_on_blank_lines() {
+        assert_eq!(sections_of("a\nb\n\nc\n"), [vec!["a", "b"], vec!["c"]]);
+        // trailing newlines and blank lines at either end make no sections
+        assert_eq!(sections_of("\n\na\n\nb\n\n\n"), [["a"], ["b"]]);
+        assert_eq!(sections_of(""), Vec::<Vec<&str>>::new());
+        assert_eq!(sections_of("\n \n\t\n"), Vec::<Vec<&str>>::new());
+        // lines of whitespace are blank, and a run of them is one break
+        assert_eq!(sections_of("a\n  \n\t\nb"), [["a"], ["b"]]);
+        // lines() drops the \r of CRLF, and a stray one is whitespace anyway
+        assert_eq!(
+            sections_of("a\r\nb\r\n\r\nc\r\n"),
+            [vec!["a", "b"], vec!["c"]]
+        );
+        assert_eq!(sections_of("a\n\r\nb"), [["a"], ["b"]]);
+    }
+
+    #[test]
+    fn sections_of_owned_lines() {
+        let lines = ["x", "", "y", "z"].map(String::from);
+        let found = sections(lines).collect::<Vec<_>>();
+        assert_eq!(found, [vec!["x"], vec!["y", "z"]]);
+    }
+
+    #[test]
+    fn sections_with_headers() {
+        let input = "--- scanner 0 ---\n1,2\n3,4\n\n--- scanner 1 ---\n\n\nseeds: 1\n";
+        let found = sections(input.lines()).with_headers().collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            [
+                ("--- scanner 0 ---", vec!["1,2", "3,4"]),
+                ("--- scanner 1 ---", vec![]),
+                ("seeds: 1", vec![]),
+            ]
+        );
+    }
+}
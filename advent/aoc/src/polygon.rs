@@ -0,0 +1,223 @@
+//! Polygons with their corners on the integer lattice, like a loop dug or
+//! walked along a grid.
+//!
+//! The area comes from the shoelace formula and the lattice points inside
+//! from Pick's theorem, `A = I + B / 2 - 1` with `I` the points strictly
+//! inside and `B` those on the boundary. Both only hold for simple polygons,
+//! whose edges don't cross or touch except where consecutive edges meet,
+//! which [`Polygon::self_intersection`] checks.
+
+use crate::geom::{Dir, Point};
+use crate::math;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+impl Polygon {
+    /// The polygon through `vertices` in order, closed by an edge from the
+    /// last back to the first. Repeated vertices, including the first one at
+    /// the end, are dropped.
+    pub fn new(mut vertices: Vec<Point>) -> Polygon {
+        vertices.dedup();
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        Polygon { vertices }
+    }
+
+    /// The polygon traced by walking `moves` from the origin, each `(dir, n)`
+    /// going `n` steps in `dir`. `None` unless the walk ends where it
+    /// started.
+    pub fn from_moves(moves: impl IntoIterator<Item = (Dir, i64)>) -> Option<Polygon> {
+        let mut pos = Point::ORIGIN;
+        let mut vertices = vec![pos];
+        for (dir, n) in moves {
+            pos = pos.moved(dir, n);
+            vertices.push(pos);
+        }
+        (pos == Point::ORIGIN).then(|| Polygon::new(vertices))
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    /// Every edge as its start and end, the last one closing the loop.
+    pub fn edges(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().copied().zip(next.copied())
+    }
+
+    /// Twice the signed area, which is always a whole number. Positive when
+    /// the vertices go clockwise on screen, i.e. counterclockwise with `y`
+    /// pointing up.
+    pub fn doubled_signed_area(&self) -> i64 {
+        self.edges().map(|(a, b)| a.cross(b)).sum()
+    }
+
+    /// Twice the area.
+    pub fn doubled_area(&self) -> u64 {
+        self.doubled_signed_area().unsigned_abs()
+    }
+
+    /// The number of lattice points on the edges.
+    pub fn boundary_points(&self) -> u64 {
+        self.edges()
+            .map(|(a, b)| math::gcd(a.x.abs_diff(b.x), a.y.abs_diff(b.y)))
+            .sum()
+    }
+
+    /// The number of lattice points strictly inside, for simple polygons.
+    pub fn interior_points(&self) -> u64 {
+        (self.doubled_area() + 2).saturating_sub(self.boundary_points()) / 2
+    }
+
+    /// The number of lattice points inside or on the boundary, for simple
+    /// polygons. With the points standing for grid cells, that's the cells
+    /// covered by a loop of cells and everything it encloses.
+    pub fn lattice_points(&self) -> u64 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    /// The indices of the first two edges that cross, touch or double back
+    /// on each other, `None` if the polygon is simple. Edge `i` goes from
+    /// vertex `i` to the next one. Checks every pair, so it's quadratic.
+    pub fn self_intersection(&self) -> Option<(usize, usize)> {
+        let edges = self.edges().collect::<Vec<_>>();
+        let n = edges.len();
+        for i in 0..n {
+            for j in i + 1..n {
+                let ((a, b), (c, d)) = (edges[i], edges[j]);
+                let hit = if j == i + 1 || (i == 0 && j == n - 1) {
+                    // neighbours share a vertex, they only overlap if one
+                    // turns right back along the other
+                    let (u, v) = if j == i + 1 {
+                        (b - a, d - c)
+                    } else {
+                        (d - c, b - a)
+                    };
+                    u.cross(v) == 0 && u.x * v.x + u.y * v.y < 0
+                } else {
+                    segments_intersect((a, b), (c, d))
+                };
+                if hit {
+                    return Some((i, j));
+                }
+            }
+        }
+        None
+    }
+
+    pub fn is_simple(&self) -> bool {
+        self.self_intersection().is_none()
+    }
+}
+
+// The sign of the turn from a to b to c, in i128 so that nothing overflows.
+fn orientation(a: Point, b: Point, c: Point) -> i128 {
+    let (ux, uy) = ((b.x - a.x) as i128, (b.y - a.y) as i128);
+    let (vx, vy) = ((c.x - a.x) as i128, (c.y - a.y) as i128);
+    (ux * vy - uy * vx).signum()
+}
+
+// Whether p, known to be on the line through a and b, lies between them.
+fn within(a: Point, b: Point, p: Point) -> bool {
+    a.x.min(b.x) <= p.x && p.x <= a.x.max(b.x) && a.y.min(b.y) <= p.y && p.y <= a.y.max(b.y)
+}
+
+// Whether two closed segments have a point in common.
+fn segments_intersect((a, b): (Point, Point), (c, d): (Point, Point)) -> bool {
+    let (o1, o2) = (orientation(c, d, a), orientation(c, d, b));
+    let (o3, o4) = (orientation(a, b, c), orientation(a, b, d));
+    if o1 * o2 < 0 && o3 * o4 < 0 {
+        return true;
+    }
+    (o1 == 0 && within(c, d, a))
+        || (o2 == 0 && within(c, d, b))
+        || (o3 == 0 && within(a, b, c))
+        || (o4 == 0 && within(a, b, d))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(vertices: &[(i64, i64)]) -> Polygon {
+        Polygon::new(vertices.iter().map(|&(x, y)| Point::new(x, y)).collect())
+    }
+
+    #[test]
+    fn interior_points() {
+        let square = polygon(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(square.doubled_area(), 32);
+        assert_eq!(square.boundary_points(), 16);
+        assert_eq!(square.interior_points(), 9);
+        assert_eq!(square.lattice_points(), 25);
+        // going the other way round flips the sign only
+        let reversed = polygon(&[(0, 4), (4, 4), (4, 0), (0, 0)]);
+        assert_eq!(
+            reversed.doubled_signed_area(),
+            -square.doubled_signed_area()
+        );
+        assert_eq!(reversed.interior_points(), 9);
+
+        // a slanted edge only has its ends on the lattice: (1, 1), (1, 2)
+        // and (2, 1) are inside
+        let triangle = polygon(&[(0, 0), (4, 0), (0, 3)]);
+        assert_eq!(triangle.boundary_points(), 4 + 1 + 3);
+        assert_eq!(triangle.interior_points(), 3);
+
+        // too thin to have an inside
+        let line = polygon(&[(0, 0), (5, 0), (5, 1), (0, 1)]);
+        assert_eq!((line.interior_points(), line.lattice_points()), (0, 12));
+    }
+
+    #[test]
+    fn lattice_points_of_a_dig_plan() {
+        // the 2023 day 18 sample
+        let plan = "R6 D5 L2 D2 R2 D2 L5 U2 L1 U2 R2 U3 L2 U2";
+        let moves = plan.split(' ').map(|m| {
+            let dir = Dir::from_letter(m.chars().next().unwrap()).unwrap();
+            (dir, m[1..].parse().unwrap())
+        });
+        let lagoon = Polygon::from_moves(moves).unwrap();
+        assert!(lagoon.is_simple());
+        assert_eq!(lagoon.boundary_points(), 38);
+        assert_eq!(lagoon.lattice_points(), 62);
+        // a walk that doesn't come back isn't a polygon
+        assert_eq!(Polygon::from_moves([(Dir::R, 2), (Dir::D, 2)]), None);
+    }
+
+    #[test]
+    fn self_intersection() {
+        // a square, with the closing vertex repeated and a straight corner
+        let square = polygon(&[(0, 0), (2, 0), (4, 0), (4, 4), (0, 4), (0, 0)]);
+        assert_eq!(square.vertices().len(), 5);
+        assert!(square.is_simple());
+
+        // the loop crosses itself: R4 D2 L2 U4 L2 D2
+        let crossing = [
+            (Dir::R, 4),
+            (Dir::D, 2),
+            (Dir::L, 2),
+            (Dir::U, 4),
+            (Dir::L, 2),
+            (Dir::D, 2),
+        ];
+        let crossing = Polygon::from_moves(crossing).unwrap();
+        assert_eq!(crossing.self_intersection(), Some((0, 3)));
+        // a bow tie
+        let bow_tie = polygon(&[(0, 0), (2, 2), (2, 0), (0, 2)]);
+        assert_eq!(bow_tie.self_intersection(), Some((0, 2)));
+        // a corner touching an edge further on
+        let touching = polygon(&[(0, 0), (4, 0), (4, 4), (2, 0), (0, 4)]);
+        assert_eq!(touching.self_intersection(), Some((0, 2)));
+        // turning right back, also where the loop closes
+        let back = polygon(&[(0, 0), (4, 0), (2, 0), (2, 2)]);
+        assert_eq!(back.self_intersection(), Some((0, 1)));
+        let back = polygon(&[(0, -1), (0, 0), (2, 0), (2, 2), (0, 2)]);
+        assert_eq!(back.self_intersection(), Some((0, 4)));
+    }
+}
@@ -0,0 +1,210 @@
+//! Exact fractions over [`BigInt`], for puzzles where floating point gets
+//! close but not quite to the integer answer.
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::bigint::BigInt;
+
+/// Always in lowest terms with a positive denominator, so equal values have
+/// equal representations.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: BigInt,
+    den: BigInt,
+}
+
+impl Rational {
+    /// `num / den`, panics if `den` is zero.
+    pub fn new(num: impl Into<BigInt>, den: impl Into<BigInt>) -> Rational {
+        let (num, den) = (num.into(), den.into());
+        assert!(!den.is_zero(), "zero denominator");
+        let g = num.gcd(&den);
+        let (mut num, mut den) = (&num / &g, &den / &g);
+        if den.is_negative() {
+            (num, den) = (-num, -den);
+        }
+        Rational { num, den }
+    }
+
+    pub fn numer(&self) -> &BigInt {
+        &self.num
+    }
+
+    pub fn denom(&self) -> &BigInt {
+        &self.den
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num.is_zero()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den.is_one()
+    }
+
+    /// The value if it's a whole number.
+    pub fn to_integer(&self) -> Option<&BigInt> {
+        self.is_integer().then_some(&self.num)
+    }
+
+    /// `1 / self`, panics if `self` is zero.
+    pub fn recip(&self) -> Rational {
+        Rational::new(self.den.clone(), self.num.clone())
+    }
+}
+
+impl From<BigInt> for Rational {
+    fn from(n: BigInt) -> Rational {
+        Rational {
+            num: n,
+            den: BigInt::from(1i64),
+        }
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Rational {
+        Rational::from(BigInt::from(n))
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Rational {
+        Rational::from(BigInt::from(n))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Rational) -> Ordering {
+        // the denominators are positive so cross multiplying keeps the order
+        (&self.num * &other.den).cmp(&(&other.num * &self.den))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Rational) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// `n` for whole numbers, `n/d` otherwise.
+impl Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Neg for &Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            num: -&self.num,
+            den: self.den.clone(),
+        }
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        -&self
+    }
+}
+
+impl Add for &Rational {
+    type Output = Rational;
+
+    fn add(self, rhs: &Rational) -> Rational {
+        Rational::new(
+            &self.num * &rhs.den + &rhs.num * &self.den,
+            &self.den * &rhs.den,
+        )
+    }
+}
+
+impl Sub for &Rational {
+    type Output = Rational;
+
+    fn sub(self, rhs: &Rational) -> Rational {
+        self + &-rhs
+    }
+}
+
+impl Mul for &Rational {
+    type Output = Rational;
+
+    fn mul(self, rhs: &Rational) -> Rational {
+        Rational::new(&self.num * &rhs.num, &self.den * &rhs.den)
+    }
+}
+
+impl Div for &Rational {
+    type Output = Rational;
+
+    /// Panics if `rhs` is zero.
+    fn div(self, rhs: &Rational) -> Rational {
+        Rational::new(&self.num * &rhs.den, &self.den * &rhs.num)
+    }
+}
+
+macro_rules! forward_binop {
+    ($($trait:ident $method:ident),*) => {$(
+        impl $trait for Rational {
+            type Output = Rational;
+
+            fn $method(self, rhs: Rational) -> Rational {
+                (&self).$method(&rhs)
+            }
+        }
+    )*};
+}
+
+forward_binop!(Add add, Sub sub, Mul mul, Div div);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(num: i64, den: i64) -> Rational {
+        Rational::new(num, den)
+    }
+
+    #[test]
+    fn lowest_terms_with_positive_denominator() {
+        assert_eq!(r(6, -8), r(-3, 4));
+        assert_eq!(r(-6, -8).to_string(), "3/4");
+        assert_eq!(r(0, -5), Rational::from(0i64));
+        assert_eq!(r(10, 5).to_integer(), Some(&BigInt::from(2i64)));
+        assert_eq!(r(10, 4).to_integer(), None);
+        assert_eq!(r(-10, 4).recip(), r(-2, 5));
+    }
+
+    #[test]
+    fn arithmetic_and_order() {
+        assert_eq!(r(1, 2) + r(1, 3), r(5, 6));
+        assert_eq!(r(1, 2) - r(5, 6), r(-1, 3));
+        assert_eq!(r(-2, 3) * r(9, 4), r(-3, 2));
+        assert_eq!(r(1, 2) / r(-1, 4), r(-2, 1));
+        assert!(r(-1, 2) < r(-1, 3));
+        assert!(r(7, 3) > r(2, 1));
+        // 1/2 + 1/6 + 1/12 + ..., which telescopes
+        let mut sum = Rational::from(0i64);
+        for k in 1..=40 {
+            sum = sum + r(1, k * (k + 1));
+        }
+        assert_eq!(sum, r(40, 41));
+        // terms well past an i128 that cancel again
+        let x = r(i64::MAX, i64::MAX - 1);
+        let x4 = &(&x * &x) * &(&x * &x);
+        assert!(x4.numer().to_i128().is_none());
+        assert_eq!(&(&(&x4 / &x) / &x) / &x, x);
+    }
+}
@@ -0,0 +1,274 @@
+//! Answers of a run along with how long they took, printed for people or
+//! as JSON or CSV for other tools.
+//!
+//! The machine formats have one record per part, with the same fields in
+//! both:
+//!
+//! ```text
+//! year,day,part,kind,answer,parse_ns,time_ns
+//! 2021,13,1,int,661,182340,95211
+//! ```
+//!
+//! `parse_ns` is the time spent parsing the input, shared by both parts of a
+//! day. Answers are always written as strings, even integers, since some
+//! don't fit in a double; `kind` tells them apart. A part that failed has
+//! the kind `error` and its message as the answer.
+
+use std::fmt::{self, Display, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::Part;
+
+/// A puzzle answer, as a part returns it.
+///
+/// Parts return anything `Into<Value>`: integers become [`Value::Int`],
+/// strings [`Value::Text`], and a `Result` its `Ok` value or, for an `Err`,
+/// [`Value::Failed`] with the error's message, which the runner reports as
+/// a failure rather than an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    Int(i128),
+    Text(String),
+    /// The part found no answer, for the given reason.
+    Failed(String),
+}
+
+impl Value {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Value::Int(_) => "int",
+            Value::Text(_) => "text",
+            Value::Failed(_) => "error",
+        }
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self, Value::Failed(_))
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Int(n) => write!(f, "{}", n),
+            Value::Text(s) | Value::Failed(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! int_values {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Value {
+            fn from(n: $t) -> Value {
+                Value::Int(n as i128)
+            }
+        }
+    )*};
+}
+
+int_values!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<String> for Value {
+    fn from(s: String) -> Value {
+        Value::Text(s)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Value {
+        Value::Text(s.to_string())
+    }
+}
+
+impl<T: Into<Value>, E: Display> From<Result<T, E>> for Value {
+    fn from(result: Result<T, E>) -> Value {
+        match result {
+            Ok(answer) => answer.into(),
+            Err(e) => Value::Failed(e.to_string()),
+        }
+    }
+}
+
+/// The answer to one part and how long it took.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartRun {
+    pub part: Part,
+    pub answer: Value,
+    pub time: Duration,
+}
+
+/// The outcome of solving some parts of a day once.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Run {
+    pub year: u16,
+    pub day: u8,
+    pub parse: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// `Part 1: ...` lines, without the timings, under a heading per day
+    /// when there are several.
+    #[default]
+    Human,
+    /// An array of part records.
+    Json,
+    /// A header line followed by a line per part.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            s => Err(format!(
+                "invalid format {:?}, expected human, json or csv",
+                s
+            )),
+        }
+    }
+}
+
+/// Writes `runs` in `format`, as one document for the machine formats.
+pub fn render(runs: &[Run], format: Format) -> String {
+    let mut out = String::new();
+    match format {
+        Format::Human => {
+            for run in runs {
+                // a single day goes without a heading
+                if runs.len() > 1 {
+                    writeln!(out, "{} day {}", run.year, run.day).unwrap();
+                }
+                for part in &run.parts {
+                    match &part.answer {
+                        Value::Failed(e) => writeln!(out, "Part {} failed: {}", part.part, e),
+                        answer => writeln!(out, "Part {}: {}", part.part, answer),
+                    }
+                    .unwrap();
+                }
+            }
+        }
+        Format::Json => {
+            let records = records(runs).map(|[year, day, part, kind, answer, parse, time]| {
+                format!(
+                    "  {{\"year\": {}, \"day\": {}, \"part\": {}, \"kind\": {}, \"answer\": {}, \
+                     \"parse_ns\": {}, \"time_ns\": {}}}",
+                    year,
+                    day,
+                    part,
+                    json_string(&kind),
+                    json_string(&answer),
+                    parse,
+                    time
+                )
+            });
+            let records = records.collect::<Vec<_>>();
+            if records.is_empty() {
+                out.push_str("[]\n");
+            } else {
+                writeln!(out, "[\n{}\n]", records.join(",\n")).unwrap();
+            }
+        }
+        Format::Csv => {
+            out.push_str("year,day,part,kind,answer,parse_ns,time_ns\n");
+            for record in records(runs) {
+                let fields = record.map(|f| csv_field(&f));
+                writeln!(out, "{}", fields.join(",")).unwrap();
+            }
+        }
+    }
+    out
+}
+
+// The fields of every part record, in the order of the CSV header.
+fn records(runs: &[Run]) -> impl Iterator<Item = [String; 7]> + '_ {
+    runs.iter().flat_map(|run| {
+        run.parts.iter().map(move |part| {
+            [
+                run.year.to_string(),
+                run.day.to_string(),
+                part.part.to_string(),
+                part.answer.kind().to_string(),
+                part.answer.to_string(),
+                run.parse.as_nanos().to_string(),
+                part.time.as_nanos().to_string(),
+            ]
+        })
+    })
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// Quoted as RFC 4180 asks when it holds a separator, quote or line break.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_keep_their_type() {
+        assert_eq!(Value::from(u64::MAX), Value::Int(u64::MAX as i128));
+        assert_eq!(Value::from(-5i32), Value::Int(-5));
+        // text that looks like a number stays text
+        assert_eq!(Value::from("007"), Value::Text("007".to_string()));
+        assert_eq!(Value::from("12".to_string()).kind(), "text");
+        assert_eq!(Value::from(Ok::<_, String>(3usize)), Value::Int(3));
+        let failed = Value::from(Err::<u8, _>("no path"));
+        assert_eq!(failed, Value::Failed("no path".to_string()));
+        assert!(failed.is_failed());
+    }
+
+    #[test]
+    fn failures_in_every_format() {
+        let run = Run {
+            year: 2023,
+            day: 8,
+            parse: Duration::from_nanos(5),
+            parts: vec![PartRun {
+                part: Part::One,
+                answer: Value::Failed("no path, \"AAA\" missing".to_string()),
+                time: Duration::from_nanos(7),
+            }],
+        };
+        let runs = [run];
+        assert_eq!(
+            render(&runs, Format::Human),
+            "Part 1 failed: no path, \"AAA\" missing\n"
+        );
+        assert_eq!(
+            render(&runs, Format::Csv),
+            "year,day,part,kind,answer,parse_ns,time_ns\n\
+             2023,8,1,error,\"no path, \"\"AAA\"\" missing\",5,7\n"
+        );
+        assert!(render(&runs, Format::Json)
+            .contains("\"kind\": \"error\", \"answer\": \"no path, \\\"AAA\\\" missing\""));
+    }
+}
@@ -0,0 +1,242 @@
+//! Shortest paths over an implicit graph of states.
+//!
+//! The caller supplies the start states, a successor function returning the
+//! neighbouring states along with the cost of moving there, and a goal
+//! predicate. [`astar`] also takes a heuristic, which must never overestimate
+//! the remaining cost for the result to be the cheapest.
+//!
+//! Every state reached is kept with its best known cost and predecessor, so
+//! the path to the goal can always be recovered with [`Search::path`].
+
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Dijkstra's algorithm, i.e. [`astar`] without a heuristic.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, goal, |_| C::default())
+}
+
+/// A* search from any of `starts` to the cheapest state satisfying `goal`.
+/// `heuristic` estimates the cost left from a state to the nearest goal.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+    mut heuristic: impl FnMut(&S) -> C,
+) -> Search<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut search = Search {
+        nodes: Vec::new(),
+        goal: None,
+        stats: Stats::default(),
+    };
+    let mut index: HashMap<S, usize> = HashMap::new();
+    // (estimated total cost, cost so far, node), cheapest first
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        if let Entry::Vacant(e) = index.entry(start.clone()) {
+            let id = search.nodes.len();
+            queue.push(Reverse((heuristic(&start), C::default(), id)));
+            search.nodes.push(Node {
+                state: start,
+                cost: C::default(),
+                parent: None,
+            });
+            e.insert(id);
+        }
+    }
+
+    while let Some(Reverse((_, cost, id))) = queue.pop() {
+        // a cheaper way here was found after this entry was queued
+        if cost > search.nodes[id].cost {
+            continue;
+        }
+        if goal(&search.nodes[id].state) {
+            search.goal = Some(id);
+            break;
+        }
+        search.stats.expanded += 1;
+
+        for (next, step) in successors(&search.nodes[id].state) {
+            let next_cost = cost + step;
+            let next_id = match index.entry(next) {
+                Entry::Occupied(e) => {
+                    let node = &mut search.nodes[*e.get()];
+                    if next_cost >= node.cost {
+                        continue;
+                    }
+                    node.cost = next_cost;
+                    node.parent = Some(id);
+                    *e.get()
+                }
+                Entry::Vacant(e) => {
+                    let next_id = search.nodes.len();
+                    search.nodes.push(Node {
+                        state: e.key().clone(),
+                        cost: next_cost,
+                        parent: Some(id),
+                    });
+                    e.insert(next_id);
+                    next_id
+                }
+            };
+            let estimate = next_cost + heuristic(&search.nodes[next_id].state);
+            queue.push(Reverse((estimate, next_cost, next_id)));
+            search.stats.queued += 1;
+        }
+    }
+    search.stats.discovered = search.nodes.len();
+    search
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// States whose successors were generated.
+    pub expanded: usize,
+    /// Distinct states reached, including the starts.
+    pub discovered: usize,
+    /// Queue pushes, counting states again whenever a cheaper way to them is
+    /// found.
+    pub queued: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Node<S, C> {
+    state: S,
+    cost: C,
+    parent: Option<usize>,
+}
+
+/// The outcome of [`dijkstra`] or [`astar`].
+#[derive(Clone, Debug)]
+pub struct Search<S, C> {
+    nodes: Vec<Node<S, C>>,
+    goal: Option<usize>,
+    pub stats: Stats,
+}
+
+impl<S, C: Copy> Search<S, C> {
+    /// The goal state reached, `None` if there is no way to one.
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.map(|id| &self.nodes[id].state)
+    }
+
+    /// The cost of the cheapest way to the goal.
+    pub fn cost(&self) -> Option<C> {
+        self.goal.map(|id| self.nodes[id].cost)
+    }
+
+    /// The states on the cheapest way from a start to the goal, both
+    /// included.
+    pub fn path(&self) -> Option<Vec<&S>> {
+        let mut path = vec![];
+        let mut id = self.goal;
+        while let Some(i) = id {
+            path.push(&self.nodes[i].state);
+            id = self.nodes[i].parent;
+        }
+        path.reverse();
+        self.goal.map(|_| path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &[&str] = &[
+        "S...#....",
+        ".##.#.##.",
+        ".#......#",
+        ".#.####..",
+        "...#....G",
+    ];
+
+    type P = (usize, usize);
+
+    fn open(p: P) -> bool {
+        MAZE[p.0].as_bytes()[p.1] != b'#'
+    }
+
+    fn moves(&(r, c): &P) -> Vec<(P, u32)> {
+        [
+            (r.wrapping_sub(1), c),
+            (r + 1, c),
+            (r, c.wrapping_sub(1)),
+            (r, c + 1),
+        ]
+        .into_iter()
+        .filter(|&(r, c)| r < MAZE.len() && c < MAZE[0].len() && open((r, c)))
+        .map(|p| (p, 1))
+        .collect()
+    }
+
+    fn to_goal(&(r, c): &P) -> u32 {
+        (r.abs_diff(4) + c.abs_diff(8)) as u32
+    }
+
+    #[test]
+    fn astar_agrees_with_dijkstra_and_expands_less() {
+        let plain = dijkstra([(0, 0)], moves, |&p| p == (4, 8));
+        let guided = astar([(0, 0)], moves, |&p| p == (4, 8), to_goal);
+        assert_eq!(plain.cost(), Some(12));
+        assert_eq!(guided.cost(), Some(12));
+        assert_eq!(guided.goal(), Some(&(4, 8)));
+        assert!(guided.stats.expanded < plain.stats.expanded);
+        assert!(plain.stats.discovered <= plain.stats.queued + 1);
+    }
+
+    #[test]
+    fn path_is_a_chain_of_moves() {
+        let search = astar([(0, 0)], moves, |&p| p == (4, 8), to_goal);
+        let path = search.path().unwrap();
+        assert_eq!(path.len(), 13);
+        assert_eq!((path[0], path[12]), (&(0, 0), &(4, 8)));
+        for pair in path.windows(2) {
+            assert!(moves(pair[0]).iter().any(|(p, _)| p == pair[1]));
+        }
+    }
+
+    #[test]
+    fn starts_unreachable_goals_and_cheaper_detours() {
+        // a start that's a goal already is a path on its own
+        let search = dijkstra([(4, 8), (0, 0)], moves, |&p| p == (4, 8));
+        assert_eq!(search.cost(), Some(0));
+        assert_eq!(search.path(), Some(vec![&(4, 8)]));
+
+        let search = dijkstra([(0, 0)], moves, |&p| p == (1, 1));
+        assert_eq!(
+            (search.cost(), search.path(), search.goal()),
+            (None, None, None)
+        );
+        assert_eq!(search.stats.expanded, search.stats.discovered);
+
+        // the direct edge costs more than going round
+        let graph = |&n: &u8| match n {
+            0 => vec![(3, 10), (1, 1)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        };
+        let search = dijkstra([0], graph, |&n| n == 3);
+        assert_eq!(search.cost(), Some(3));
+        assert_eq!(search.path(), Some(vec![&0, &1, &2, &3]));
+    }
+}
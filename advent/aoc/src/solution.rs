@@ -0,0 +1,240 @@
+use std::env;
+use std::fmt::{self, Display};
+use std::io::{self, Read};
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::bench;
+use crate::image::Palette;
+use crate::parse::ParseResult;
+use crate::report::{PartRun, Run, Value};
+use crate::viz::{Screen, Visualize};
+
+/// A single day's puzzle: the input is parsed once and shared by both parts.
+pub trait Solution {
+    /// Parsed form of the puzzle input, allowed to borrow from the raw text.
+    type Input<'a>;
+
+    fn parse(input: &str) -> ParseResult<Self::Input<'_>>;
+    fn part1(input: &Self::Input<'_>) -> impl Into<Value>;
+    fn part2(input: &Self::Input<'_>) -> impl Into<Value>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            s => Err(format!("invalid part {:?}, expected 1 or 2", s)),
+        }
+    }
+}
+
+// The parse time and each part's answer, without the year and day of a Run.
+type Timed = (Duration, Vec<PartRun>);
+
+type Play = fn(&str, Part, &mut dyn Screen) -> ParseResult<()>;
+
+/// Type-erased entry of a year's solution table.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    solve: fn(&str, &[Part]) -> ParseResult<Timed>,
+    time: fn(&str) -> ParseResult<[Duration; 3]>,
+    viz: Option<(fn() -> Palette, Play)>,
+}
+
+impl Day {
+    pub const fn new<S: Solution>(day: u8) -> Self {
+        Day {
+            day,
+            solve: solve::<S>,
+            time: bench::time::<S>,
+            viz: None,
+        }
+    }
+
+    /// A day that can also be watched with `aoc viz`.
+    pub const fn with_viz<S: Visualize>(day: u8) -> Self {
+        let mut entry = Day::new::<S>(day);
+        entry.viz = Some((S::palette, play::<S>));
+        entry
+    }
+
+    /// Parses `input` once and returns the answers of `parts`, in order.
+    pub fn solve(&self, input: &str, parts: &[Part]) -> ParseResult<Vec<Value>> {
+        let (_, parts) = (self.solve)(input, parts).map_err(|e| e.locate(input))?;
+        Ok(parts.into_iter().map(|p| p.answer).collect())
+    }
+
+    /// Like [`Day::solve`], but keeps the type of each answer and how long
+    /// every stage took, for the day of `year` this is.
+    pub fn run(&self, year: u16, input: &str, parts: &[Part]) -> ParseResult<Run> {
+        let (parse, parts) = (self.solve)(input, parts).map_err(|e| e.locate(input))?;
+        Ok(Run {
+            year,
+            day: self.day,
+            parse,
+            parts,
+        })
+    }
+
+    /// Runs every stage once on `input` and returns how long each took, see
+    /// [`bench::Stage`].
+    pub fn time(&self, input: &str) -> ParseResult<[Duration; 3]> {
+        (self.time)(input).map_err(|e| e.locate(input))
+    }
+
+    /// The colours of the day's visualisation, `None` if it has none.
+    pub fn palette(&self) -> Option<Palette> {
+        self.viz.map(|(palette, _)| palette())
+    }
+
+    /// Parses `input` and plays the simulation behind `part` on `screen`.
+    /// Panics if the day has no visualisation.
+    pub fn visualize(&self, input: &str, part: Part, screen: &mut dyn Screen) -> ParseResult<()> {
+        let (_, play) = self.viz.expect("the day has no visualisation");
+        play(input, part, screen).map_err(|e| e.locate(input))
+    }
+
+    /// Entry point of the day's own binary: solves the parts given as
+    /// arguments, both by default, for the input on stdin and prints an
+    /// answer per line.
+    pub fn main(&self) {
+        let run = || -> Result<Vec<Value>, String> {
+            let mut parts = env::args()
+                .skip(1)
+                .map(|arg| arg.parse())
+                .collect::<Result<Vec<Part>, _>>()?;
+            if parts.is_empty() {
+                parts = Part::ALL.to_vec();
+            }
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("failed to read stdin: {}", e))?;
+            self.solve(&input, &parts)
+                .map_err(|e| match e.excerpt(&input) {
+                    Some(excerpt) => format!("malformed input, {}\n{}", e, excerpt),
+                    None => format!("malformed input, {}", e),
+                })
+        };
+        let answers = run().unwrap_or_else(|e| vec![Value::Failed(e)]);
+        for answer in &answers {
+            match answer {
+                Value::Failed(e) => eprintln!("error: {}", e),
+                answer => println!("{}", answer),
+            }
+        }
+        if answers.iter().any(Value::is_failed) {
+            process::exit(1);
+        }
+    }
+}
+
+/// A year's solution table along with the directory holding its inputs.
+pub struct Year {
+    pub year: u16,
+    pub days: &'static [Day],
+    pub input_dir: &'static str,
+    /// Contents of the year's `answers.txt`, see [`crate::answers`].
+    pub answers: &'static str,
+}
+
+impl Year {
+    pub fn day(&self, day: u8) -> Option<&Day> {
+        self.days.iter().find(|d| d.day == day)
+    }
+}
+
+fn solve<S: Solution>(input: &str, parts: &[Part]) -> ParseResult<Timed> {
+    let start = Instant::now();
+    let data = S::parse(input)?;
+    let parse = start.elapsed();
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            // a part that panics fails on its own, the other keeps its answer
+            let answer = panic::catch_unwind(AssertUnwindSafe(|| match part {
+                Part::One => S::part1(&data).into(),
+                Part::Two => S::part2(&data).into(),
+            }))
+            .unwrap_or_else(|_| Value::Failed("panicked".to_string()));
+            PartRun {
+                part,
+                answer,
+                time: start.elapsed(),
+            }
+        })
+        .collect();
+    Ok((parse, parts))
+}
+
+fn play<S: Visualize>(input: &str, part: Part, screen: &mut dyn Screen) -> ParseResult<()> {
+    let data = S::parse(input)?;
+    S::visualize(&data, part, screen);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Counts the lines, and gives up on part 2.
+    struct Flaky;
+
+    impl Solution for Flaky {
+        type Input<'a> = usize;
+
+        fn parse(input: &str) -> ParseResult<Self::Input<'_>> {
+            Ok(input.lines().count())
+        }
+
+        fn part1(lines: &Self::Input<'_>) -> impl Into<Value> {
+            *lines as u64
+        }
+
+        fn part2(_: &Self::Input<'_>) -> impl Into<Value> {
+            if true {
+                panic!("part 2 is broken");
+            }
+            0u64
+        }
+    }
+
+    #[test]
+    fn a_panic_only_fails_its_part() {
+        let day = Day::new::<Flaky>(1);
+        assert_eq!(
+            day.solve("a\nb\n", &Part::ALL).unwrap(),
+            [Value::Int(2), Value::Failed("panicked".to_string())]
+        );
+        let run = day.run(2023, "a\n", &[Part::Two, Part::One]).unwrap();
+        assert!(run.parts[0].answer.is_failed());
+        assert_eq!(run.parts[1].answer, Value::Int(1));
+    }
+}
@@ -0,0 +1,450 @@
+//! Points, rotations and rigid transforms in 3D, and aligning clouds of
+//! points measured in different frames.
+//!
+//! The rotations are the symmetries of a cube, i.e. every way to turn the
+//! axes onto each other, so they map integer points to integer points.
+
+use std::collections::{HashMap, HashSet};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Vec3 {
+    pub const ORIGIN: Vec3 = Vec3 { x: 0, y: 0, z: 0 };
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    pub fn coords(self) -> [i64; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    pub fn manhattan(self, other: Vec3) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// The squared length, which every rotation keeps.
+    pub fn norm2(self) -> i64 {
+        self.x * self.x + self.y * self.y + self.z * self.z
+    }
+}
+
+impl From<[i64; 3]> for Vec3 {
+    fn from([x, y, z]: [i64; 3]) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, rhs: Vec3) -> Vec3 {
+        Vec3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for Vec3 {
+    fn add_assign(&mut self, rhs: Vec3) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, rhs: Vec3) -> Vec3 {
+        Vec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl SubAssign for Vec3 {
+    fn sub_assign(&mut self, rhs: Vec3) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Vec3 {
+    type Output = Vec3;
+
+    fn mul(self, rhs: i64) -> Vec3 {
+        Vec3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Vec3;
+
+    fn neg(self) -> Vec3 {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+/// A symmetry of the cube, which may be a reflection: axis `i` of the result
+/// is `sign[i]` times axis `perm[i]` of the input. `a * b` applies `b` first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rotation {
+    perm: [usize; 3],
+    sign: [i64; 3],
+}
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation {
+        perm: [0, 1, 2],
+        sign: [1, 1, 1],
+    };
+    /// A quarter turn about the x axis, taking y to z.
+    pub const QUARTER_X: Rotation = Rotation {
+        perm: [0, 2, 1],
+        sign: [1, -1, 1],
+    };
+    /// A quarter turn about the y axis, taking z to x.
+    pub const QUARTER_Y: Rotation = Rotation {
+        perm: [2, 1, 0],
+        sign: [1, 1, -1],
+    };
+    /// A quarter turn about the z axis, taking x to y.
+    pub const QUARTER_Z: Rotation = Rotation {
+        perm: [1, 0, 2],
+        sign: [-1, 1, 1],
+    };
+    /// The mirror image across the yz plane.
+    pub const REFLECT_X: Rotation = Rotation {
+        perm: [0, 1, 2],
+        sign: [-1, 1, 1],
+    };
+
+    /// The 24 proper rotations, starting with the identity.
+    pub fn all() -> Vec<Rotation> {
+        generate(&[Rotation::QUARTER_X, Rotation::QUARTER_Y])
+    }
+
+    /// All 48 symmetries, the rotations followed by their mirror images.
+    pub fn all_with_reflections() -> Vec<Rotation> {
+        let rotations = Rotation::all();
+        let mirrored = rotations.iter().map(|&r| r * Rotation::REFLECT_X);
+        rotations.iter().copied().chain(mirrored).collect()
+    }
+
+    pub fn apply(self, v: Vec3) -> Vec3 {
+        let c = v.coords();
+        Vec3::from(std::array::from_fn(|i| self.sign[i] * c[self.perm[i]]))
+    }
+
+    pub fn inverse(self) -> Rotation {
+        let mut inv = Rotation::IDENTITY;
+        for i in 0..3 {
+            inv.perm[self.perm[i]] = i;
+            inv.sign[self.perm[i]] = self.sign[i];
+        }
+        inv
+    }
+
+    /// 1 for proper rotations, -1 for reflections.
+    pub fn determinant(self) -> i64 {
+        // the parity of a permutation of three is the parity of its fixed points
+        let fixed = (0..3).filter(|&i| self.perm[i] == i).count();
+        let parity = if fixed == 1 { -1 } else { 1 };
+        parity * self.sign.iter().product::<i64>()
+    }
+}
+
+impl Mul for Rotation {
+    type Output = Rotation;
+
+    fn mul(self, rhs: Rotation) -> Rotation {
+        Rotation {
+            perm: self.perm.map(|p| rhs.perm[p]),
+            sign: std::array::from_fn(|i| self.sign[i] * rhs.sign[self.perm[i]]),
+        }
+    }
+}
+
+// The group generated by `generators`, breadth first from the identity.
+fn generate(generators: &[Rotation]) -> Vec<Rotation> {
+    let mut group = vec![Rotation::IDENTITY];
+    let mut i = 0;
+    while i < group.len() {
+        for &g in generators {
+            let next = g * group[i];
+            if !group.contains(&next) {
+                group.push(next);
+            }
+        }
+        i += 1;
+    }
+    group
+}
+
+/// A rotation followed by a translation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Transform {
+    pub rotation: Rotation,
+    pub translation: Vec3,
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        rotation: Rotation::IDENTITY,
+        translation: Vec3::ORIGIN,
+    };
+
+    pub fn apply(self, v: Vec3) -> Vec3 {
+        self.rotation.apply(v) + self.translation
+    }
+
+    pub fn inverse(self) -> Transform {
+        let rotation = self.rotation.inverse();
+        Transform {
+            rotation,
+            translation: -rotation.apply(self.translation),
+        }
+    }
+}
+
+/// `a * b` applies `b` first, like for [`Rotation`]s.
+impl Mul for Transform {
+    type Output = Transform;
+
+    fn mul(self, rhs: Transform) -> Transform {
+        Transform {
+            rotation: self.rotation * rhs.rotation,
+            translation: self.apply(rhs.translation),
+        }
+    }
+}
+
+/// A set of points along with the pairs of them grouped by their squared
+/// distance. Distances don't depend on the frame the points were measured
+/// in, so clouds sharing points share the distances between them: that rules
+/// out most clouds before trying to align them and suggests which points to
+/// match up when aligning.
+#[derive(Clone, Debug)]
+pub struct Cloud {
+    points: Vec<Vec3>,
+    lookup: HashSet<Vec3>,
+    pairs: HashMap<i64, Vec<(usize, usize)>>,
+}
+
+impl Cloud {
+    pub fn new(points: Vec<Vec3>) -> Cloud {
+        let mut pairs: HashMap<_, Vec<_>> = HashMap::new();
+        for (i, &a) in points.iter().enumerate() {
+            for (j, &b) in points.iter().enumerate().skip(i + 1) {
+                pairs.entry((a - b).norm2()).or_default().push((i, j));
+            }
+        }
+        let lookup = points.iter().copied().collect();
+        Cloud {
+            points,
+            lookup,
+            pairs,
+        }
+    }
+
+    pub fn points(&self) -> &[Vec3] {
+        &self.points
+    }
+
+    /// Whether the clouds have enough pair distances in common to possibly
+    /// share `k` points: those make `k * (k - 1) / 2` pairs.
+    pub fn may_overlap(&self, other: &Cloud, k: usize) -> bool {
+        let common: usize = self
+            .pairs
+            .iter()
+            .map(|(d, ps)| other.pairs.get(d).map_or(0, |qs| ps.len().min(qs.len())))
+            .sum();
+        common >= k * k.saturating_sub(1) / 2
+    }
+
+    /// A transform taking at least `k` points of `other` onto points of
+    /// `self`, using one of `rotations`. Works from pairs of points, so `k`
+    /// should be at least 2.
+    pub fn align(&self, other: &Cloud, k: usize, rotations: &[Rotation]) -> Option<Transform> {
+        if !self.may_overlap(other, k) {
+            return None;
+        }
+        // if a pair of points in self is a pair in other, one of the
+        // rotations turns the vector between them into the other's, either
+        // way round, which also pins down the translation
+        for (d, ps) in &self.pairs {
+            let Some(qs) = other.pairs.get(d) else {
+                continue;
+            };
+            for &(i, j) in ps {
+                let (a, a_to) = (self.points[i], self.points[j] - self.points[i]);
+                for &(p, q) in qs {
+                    let (b, c) = (other.points[p], other.points[q]);
+                    for &rotation in rotations {
+                        for (from, to) in [(b, c), (c, b)] {
+                            if rotation.apply(to - from) != a_to {
+                                continue;
+                            }
+                            let t = Transform {
+                                rotation,
+                                translation: a - rotation.apply(from),
+                            };
+                            if self.matches(other, t) >= k {
+                                return Some(t);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    // The number of points of other landing on points of self.
+    fn matches(&self, other: &Cloud, t: Transform) -> usize {
+        other
+            .points
+            .iter()
+            .filter(|&&p| self.lookup.contains(&t.apply(p)))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const X: Vec3 = Vec3::new(1, 0, 0);
+    const Y: Vec3 = Vec3::new(0, 1, 0);
+    const Z: Vec3 = Vec3::new(0, 0, 1);
+
+    #[test]
+    fn rotation_group() {
+        let rotations = Rotation::all();
+        assert_eq!(rotations.len(), 24);
+        assert_eq!(rotations[0], Rotation::IDENTITY);
+        assert_eq!(rotations.iter().collect::<HashSet<_>>().len(), 24);
+        assert!(rotations.iter().all(|r| r.determinant() == 1));
+
+        let all = Rotation::all_with_reflections();
+        assert_eq!(all.len(), 48);
+        assert_eq!(all.iter().collect::<HashSet<_>>().len(), 48);
+        assert!(all[24..].iter().all(|r| r.determinant() == -1));
+        let v = Vec3::new(1, 2, 3);
+        for &r in &all {
+            assert_eq!(r * r.inverse(), Rotation::IDENTITY, "{:?}", r);
+            assert_eq!(r.inverse() * r, Rotation::IDENTITY, "{:?}", r);
+            assert_eq!(r.apply(v).norm2(), v.norm2());
+            for &s in &all {
+                assert_eq!((r * s).apply(v), r.apply(s.apply(v)));
+            }
+        }
+        // every symmetry sends (1, 2, 3) somewhere else
+        assert_eq!(
+            all.iter().map(|r| r.apply(v)).collect::<HashSet<_>>().len(),
+            48
+        );
+    }
+
+    #[test]
+    fn quarter_turns() {
+        assert_eq!(Rotation::QUARTER_X.apply(Y), Z);
+        assert_eq!(Rotation::QUARTER_Y.apply(Z), X);
+        assert_eq!(Rotation::QUARTER_Z.apply(X), Y);
+        assert_eq!(
+            Rotation::REFLECT_X.apply(Vec3::new(1, 2, 3)),
+            Vec3::new(-1, 2, 3)
+        );
+        let four = |r: Rotation| r * r * r * r;
+        for r in [
+            Rotation::QUARTER_X,
+            Rotation::QUARTER_Y,
+            Rotation::QUARTER_Z,
+        ] {
+            assert_eq!(four(r), Rotation::IDENTITY);
+            assert_ne!(r * r, Rotation::IDENTITY);
+        }
+    }
+
+    #[test]
+    fn transform_inverse() {
+        let all = Rotation::all_with_reflections();
+        let v = Vec3::new(-7, 4, 11);
+        for (i, &rotation) in all.iter().enumerate() {
+            let t = Transform {
+                rotation,
+                translation: Vec3::new(i as i64, -3, 100),
+            };
+            assert_eq!(t * t.inverse(), Transform::IDENTITY);
+            assert_eq!(t.inverse() * t, Transform::IDENTITY);
+            assert_eq!(t.inverse().apply(t.apply(v)), v);
+            let u = Transform {
+                rotation: all[47 - i],
+                translation: v,
+            };
+            assert_eq!((t * u).apply(v), t.apply(u.apply(v)));
+        }
+    }
+
+    // Points with no symmetry to speak of, so only one transform lines them
+    // up with a moved copy.
+    fn scattered(n: usize) -> Vec<Vec3> {
+        let mut state = 0x9e37_79b9_u64;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as i64 % 1000 - 500
+        };
+        (0..n).map(|_| Vec3::new(next(), next(), next())).collect()
+    }
+
+    #[test]
+    fn align_a_moved_copy() {
+        let points = scattered(20);
+        let moved = Transform {
+            rotation: Rotation::QUARTER_X * Rotation::QUARTER_Z * Rotation::QUARTER_Z,
+            translation: Vec3::new(1105, -68, 1246),
+        };
+        // the copy shares 12 points and has a few of its own
+        let mut other = points[8..]
+            .iter()
+            .map(|&p| moved.apply(p))
+            .collect::<Vec<_>>();
+        other.extend(scattered(25)[20..].iter().map(|&p| p * 3));
+        let (here, there) = (Cloud::new(points.clone()), Cloud::new(other));
+
+        let t = here.align(&there, 12, &Rotation::all()).unwrap();
+        assert_eq!(t, moved.inverse());
+        for &p in &points[8..] {
+            assert_eq!(t.apply(moved.apply(p)), p);
+        }
+        // the other way round too
+        assert_eq!(there.align(&here, 12, &Rotation::all()), Some(moved));
+        // but not with more points in common than there are
+        assert_eq!(here.align(&there, 13, &Rotation::all()), None);
+        assert!(!here.may_overlap(&Cloud::new(scattered(25)[20..].to_vec()), 3));
+    }
+
+    #[test]
+    fn align_a_mirror_image() {
+        let points = scattered(15);
+        let mirrored = Transform {
+            rotation: Rotation::QUARTER_Y * Rotation::REFLECT_X,
+            translation: Vec3::new(-20, 30, 7),
+        };
+        let other = points
+            .iter()
+            .map(|&p| mirrored.apply(p))
+            .collect::<Vec<_>>();
+        let (here, there) = (Cloud::new(points), Cloud::new(other));
+        // the distances all match, but no proper rotation does
+        assert!(here.may_overlap(&there, 15));
+        assert_eq!(here.align(&there, 15, &Rotation::all()), None);
+        let t = here
+            .align(&there, 15, &Rotation::all_with_reflections())
+            .unwrap();
+        assert_eq!(t, mirrored.inverse());
+        assert_eq!(t.rotation.determinant(), -1);
+    }
+}
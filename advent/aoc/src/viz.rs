@@ -0,0 +1,59 @@
+//! Simulations shown a step at a time, for `aoc viz`.
+//!
+//! A day implementing [`Visualize`] plays its simulation by handing each
+//! [`Scene`] to a [`Screen`], which may be the terminal viewer or a GIF
+//! being written. The screen decides the pace, so the day just loops.
+
+use crate::grid::Pos;
+use crate::image::{Frame, Palette};
+use crate::{Grid, Part, Solution};
+
+/// One step of a simulation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scene {
+    /// A character and a colour of the day's palette per cell.
+    pub cells: Grid<(char, u8)>,
+    /// Cells to highlight on top of the picture, like the way something just
+    /// went.
+    pub path: Vec<Pos>,
+    /// A line shown under the picture, e.g. the step number.
+    pub caption: String,
+}
+
+impl Scene {
+    pub fn new(cells: Grid<(char, u8)>, caption: String) -> Scene {
+        Scene {
+            cells,
+            path: vec![],
+            caption,
+        }
+    }
+
+    pub fn with_path(mut self, path: Vec<Pos>) -> Scene {
+        self.path = path;
+        self
+    }
+
+    /// The picture with the path drawn in colour `highlight`.
+    pub fn frame(&self, highlight: u8) -> Frame {
+        let mut frame = Frame::from_grid(&self.cells, |&(_, color)| color);
+        for &(r, c) in &self.path {
+            frame.set(c, r, highlight);
+        }
+        frame
+    }
+}
+
+pub trait Screen {
+    /// Shows the next scene, `false` once nothing more is wanted.
+    fn show(&mut self, scene: &Scene) -> bool;
+}
+
+pub trait Visualize: Solution {
+    /// The colours cells are drawn in.
+    fn palette() -> Palette;
+
+    /// Plays the simulation behind `part` on `screen`, stopping as soon as
+    /// it returns `false`.
+    fn visualize(input: &Self::Input<'_>, part: Part, screen: &mut dyn Screen);
+}
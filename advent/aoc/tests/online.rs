@@ -0,0 +1,221 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc::guesses::{Guess, History, Outcome, Rejection};
+use aoc::mock::Server;
+use aoc::online::{self, Client, Fetched, OnlineError, Verdict};
+use aoc::Part;
+
+// A fresh directory per test, under the system's temporary one.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn fetch_downloads_once() {
+    let server = Server::start("cookie").unwrap();
+    server.add_input(2022, 3, "vJrwpWtwJgWrhcsFMMfFFhFp\n");
+    let mut client = Client::new(&server.url(), "cookie").with_throttle(Duration::ZERO, None);
+    let dir = scratch_dir("fetch");
+    let path = dir.join("03.txt");
+
+    let fetched = online::fetch_input(&mut client, &dir, 2022, 3).unwrap();
+    assert_eq!(fetched, Fetched::Downloaded(path.clone()));
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        "vJrwpWtwJgWrhcsFMMfFFhFp\n"
+    );
+    let fetched = online::fetch_input(&mut client, &dir, 2022, 3).unwrap();
+    assert_eq!(fetched, Fetched::Cached(path));
+    assert_eq!(server.requests(), ["GET /2022/day/3/input"]);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn fetch_errors() {
+    let server = Server::start("cookie").unwrap();
+    server.add_input(2022, 3, "input\n");
+    let dir = scratch_dir("fetch-errors");
+
+    let mut stranger = Client::new(&server.url(), "other").with_throttle(Duration::ZERO, None);
+    let result = online::fetch_input(&mut stranger, &dir, 2022, 3);
+    assert!(
+        matches!(result, Err(OnlineError::LoggedOut)),
+        "{:?}",
+        result
+    );
+
+    let mut client = Client::new(&server.url(), "cookie").with_throttle(Duration::ZERO, None);
+    let result = online::fetch_input(&mut client, &dir, 2022, 4);
+    assert!(
+        matches!(result, Err(OnlineError::Locked(_))),
+        "{:?}",
+        result
+    );
+    // nothing is left behind to pass for a cached input
+    assert!(!dir.join("04.txt").exists());
+
+    // puzzles that aren't out don't even get asked for
+    let result = client.input(9999, 1);
+    assert!(
+        matches!(result, Err(OnlineError::Locked(_))),
+        "{:?}",
+        result
+    );
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn throttle_spans_clients() {
+    let server = Server::start("cookie").unwrap();
+    server.add_input(2022, 1, "1\n");
+    let dir = scratch_dir("throttle");
+    let state_dir = Some(dir.clone());
+    let interval = Duration::from_millis(300);
+
+    let start = Instant::now();
+    for _ in 0..3 {
+        // a new client each time, like separate runs of the CLI
+        let mut client =
+            Client::new(&server.url(), "cookie").with_throttle(interval, state_dir.clone());
+        client.input(2022, 1).unwrap();
+    }
+    assert!(start.elapsed() >= 2 * interval);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn submit_verdicts() {
+    let server = Server::start("cookie").unwrap();
+    server.add_answer(2022, 5, Part::One, "150");
+    server.set_penalty(Duration::from_secs(1));
+    let mut client = Client::new(&server.url(), "cookie").with_throttle(Duration::ZERO, None);
+
+    let verdict = client.submit(2022, 5, Part::Two, "1").unwrap();
+    assert_eq!(verdict, Verdict::WrongLevel);
+    let verdict = client.submit(2022, 5, Part::One, "100").unwrap();
+    assert_eq!(
+        verdict,
+        Verdict::Judged(Outcome::TooLow, Duration::from_secs(1))
+    );
+    // held back without asking the site
+    let verdict = client.submit(2022, 5, Part::One, "200").unwrap();
+    assert!(matches!(verdict, Verdict::Wait(_)), "{:?}", verdict);
+    assert_eq!(server.requests().len(), 2);
+
+    // another client doesn't know and is told off by the site
+    let mut other = Client::new(&server.url(), "cookie").with_throttle(Duration::ZERO, None);
+    let verdict = other.submit(2022, 5, Part::One, "200").unwrap();
+    assert_eq!(verdict, Verdict::Wait(Duration::from_secs(1)));
+
+    thread::sleep(Duration::from_secs(1));
+    let verdict = client.submit(2022, 5, Part::One, "150").unwrap();
+    assert_eq!(verdict, Verdict::Judged(Outcome::Right, Duration::ZERO));
+    let verdict = client.submit(2022, 5, Part::One, "150").unwrap();
+    assert_eq!(verdict, Verdict::WrongLevel);
+
+    let mut stranger = Client::new(&server.url(), "other").with_throttle(Duration::ZERO, None);
+    let result = stranger.submit(2022, 5, Part::Two, "1");
+    assert!(
+        matches!(result, Err(OnlineError::LoggedOut)),
+        "{:?}",
+        result
+    );
+}
+
+#[test]
+fn verdicts_of_the_site() {
+    let page = |text: &str| format!("<main>\n<article><p>{}</p></article>\n</main>", text);
+    let cases = [
+        (
+            "That's the right answer!  You are <span class=\"day-success\">one gold star</span> \
+             closer to restoring snow operations.",
+            Verdict::Judged(Outcome::Right, Duration::ZERO),
+        ),
+        (
+            "That's not the right answer; your answer is too high.  If you're stuck, make sure \
+             you're using the full input data.  Please wait one minute before trying again.",
+            Verdict::Judged(Outcome::TooHigh, Duration::from_secs(60)),
+        ),
+        (
+            "That's not the right answer.  If you're stuck, make sure you're using the full \
+             input data; there are also some general tips on the <a href=\"/2023/about\">about \
+             page</a>.  Because you have guessed incorrectly 4 times on this puzzle, please \
+             wait 5 minutes before trying again.",
+            Verdict::Judged(Outcome::Wrong, Duration::from_secs(300)),
+        ),
+        (
+            "You gave an answer too recently; you have to wait after submitting an answer \
+             before trying again.  You have 4m 23s left to wait.",
+            Verdict::Wait(Duration::from_secs(263)),
+        ),
+        (
+            "You don't seem to be solving the right level.  Did you already complete it?",
+            Verdict::WrongLevel,
+        ),
+    ];
+    for (text, verdict) in cases {
+        assert_eq!(
+            online::parse_verdict(&page(text)),
+            Some(verdict),
+            "{}",
+            text
+        );
+    }
+    assert_eq!(online::parse_verdict("<html>maintenance</html>"), None);
+}
+
+#[test]
+fn history_rejects_hopeless_guesses() {
+    let dir = scratch_dir("history");
+    let path = dir.join("guesses.txt");
+    let mut history = History::load(&path).unwrap();
+    let guess = |part, outcome, answer: &str| Guess {
+        year: 2023,
+        day: 7,
+        part,
+        outcome,
+        answer: answer.to_string(),
+    };
+    history
+        .record(guess(Part::One, Outcome::TooHigh, "900"))
+        .unwrap();
+    history
+        .record(guess(Part::One, Outcome::TooLow, "100"))
+        .unwrap();
+    history
+        .record(guess(Part::One, Outcome::Wrong, "500"))
+        .unwrap();
+    history
+        .record(guess(Part::Two, Outcome::Right, "x"))
+        .unwrap();
+
+    // read back from the file
+    let history = History::load(&path).unwrap();
+    assert_eq!(history.guesses().len(), 4);
+    let bounds = Err(Rejection::OutOfBounds {
+        low: Some(100),
+        high: Some(900),
+    });
+    assert_eq!(history.check(2023, 7, Part::One, "901"), bounds);
+    assert_eq!(history.check(2023, 7, Part::One, "50"), bounds);
+    assert_eq!(
+        history.check(2023, 7, Part::One, "500"),
+        Err(Rejection::Repeated(Outcome::Wrong))
+    );
+    assert_eq!(history.check(2023, 7, Part::One, "499"), Ok(()));
+    assert_eq!(history.check(2023, 8, Part::One, "901"), Ok(()));
+    assert_eq!(
+        history.check(2023, 7, Part::Two, "y"),
+        Err(Rejection::Solved("x".to_string()))
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
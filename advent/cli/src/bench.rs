@@ -0,0 +1,148 @@
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::time::Duration;
+
+use aoc::bench::{self, Baseline, Stage};
+use aoc::input::{self, InputError};
+use aoc::Variant;
+
+use crate::{find_day, find_year, parse_error, parse_sample};
+
+const USAGE: &str = "\
+usage: aoc bench <year> [<day>] [-n <iterations>] [--sample[=<n>]]
+                 [--baseline <file>] [--save <file>]
+
+Times parse, part 1 and part 2 separately. --baseline compares the means with a
+file written by an earlier --save; saving into an existing file updates it.";
+
+pub fn cmd_bench(args: &[String]) -> Result<(), String> {
+    let mut positional = Vec::new();
+    let mut iterations = 10;
+    let mut sample = None;
+    let mut baseline_path = None;
+    let mut save_path = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-n" | "--iterations" => {
+                let n = args.next().ok_or("--iterations expects a value")?;
+                iterations = n
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or_else(|| format!("invalid iteration count {:?}", n))?;
+            }
+            "--baseline" => baseline_path = Some(args.next().ok_or("--baseline expects a file")?),
+            "--save" => save_path = Some(args.next().ok_or("--save expects a file")?),
+            arg => match parse_sample(arg)? {
+                Some(n) => sample = Some(n),
+                None => positional.push(arg),
+            },
+        }
+    }
+    let (year, day) = match positional[..] {
+        [year] => (year, None),
+        [year, day] => (year, Some(day)),
+        _ => return Err(USAGE.to_string()),
+    };
+    let year = find_year(year)?;
+    let whole_year = day.is_none();
+    let days = match day {
+        Some(day) => vec![find_day(year, day)?],
+        None => year.days.iter().collect(),
+    };
+    let variant = sample.map_or(Variant::Puzzle, Variant::Sample);
+
+    let baseline = match baseline_path {
+        Some(path) => read_baseline(path)?,
+        None => Baseline::default(),
+    };
+    let mut results = Baseline::default();
+
+    println!(
+        "{:>3}  {:<5} {:>10} {:>10} {:>10} {:>9}",
+        "day", "stage", "mean", "min", "stddev", "baseline"
+    );
+    for day in days {
+        let input = match input::read(Path::new(year.input_dir), day.day, variant) {
+            Ok(input) => input,
+            // Benchmarking a whole year shouldn't stop at the first missing input.
+            Err(InputError::NotFound { .. }) if whole_year => {
+                println!("{:>3}  no {}", day.day, variant);
+                continue;
+            }
+            Err(e) => return Err(e.to_string()),
+        };
+        let measured =
+            panic::catch_unwind(AssertUnwindSafe(|| bench::measure(day, &input, iterations)));
+        let stats = match measured {
+            Ok(Ok(stats)) => stats,
+            Ok(Err(e)) => return Err(parse_error(&e, &input)),
+            Err(_) => {
+                println!("{:>3}  panicked", day.day);
+                continue;
+            }
+        };
+        for (i, (stage, stats)) in Stage::ALL.into_iter().zip(stats).enumerate() {
+            let key = (year.year, day.day, stage);
+            let diff = baseline.0.get(&key).map_or(String::new(), |base| {
+                let base = base.mean.as_secs_f64();
+                // a stage too quick to measure has nothing to compare with
+                if base == 0.0 {
+                    return "n/a".to_string();
+                }
+                let change = (stats.mean.as_secs_f64() - base) / base * 100.0;
+                format!("{:+.1}%", change)
+            });
+            let day = if i == 0 {
+                day.day.to_string()
+            } else {
+                String::new()
+            };
+            let line = format!(
+                "{:>3}  {:<5} {:>10} {:>10} {:>10} {:>9}",
+                day,
+                stage,
+                fmt_duration(stats.mean),
+                fmt_duration(stats.min),
+                fmt_duration(stats.stddev),
+                diff
+            );
+            println!("{}", line.trim_end());
+            results.0.insert(key, stats);
+        }
+    }
+    let total: Duration = results.0.values().map(|s| s.mean).sum();
+    println!("total {}", fmt_duration(total));
+
+    if let Some(path) = save_path {
+        let mut saved = if Path::new(path).exists() {
+            read_baseline(path)?
+        } else {
+            Baseline::default()
+        };
+        saved.0.extend(results.0);
+        fs::write(path, saved.to_string())
+            .map_err(|e| format!("failed to write {}: {}", path, e))?;
+    }
+    Ok(())
+}
+
+fn read_baseline(path: &str) -> Result<Baseline, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path, e))?;
+    Baseline::parse(&text).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn fmt_duration(d: Duration) -> String {
+    let secs = d.as_secs_f64();
+    if secs < 1e-6 {
+        format!("{}ns", d.as_nanos())
+    } else if secs < 1e-3 {
+        format!("{:.1}µs", secs * 1e6)
+    } else if secs < 1.0 {
+        format!("{:.2}ms", secs * 1e3)
+    } else {
+        format!("{:.3}s", secs)
+    }
+}
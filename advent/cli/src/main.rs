@@ -0,0 +1,198 @@
+use std::env;
+use std::io::{self, Read};
+use std::panic;
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+
+use aoc::input::{self, InputError};
+use aoc::report::{self, Format, PartRun, Run};
+use aoc::{Day, ParseError, Part, Value, Variant, Year};
+
+mod bench;
+mod online;
+mod viz;
+
+const USAGE: &str = "\
+usage: aoc run <year> [<day> [<file>|-]] [--part <1|2>] [--sample[=<n>]]
+               [--format <human|json|csv>]
+       aoc bench <year> [<day>] [-n <iterations>] [--sample[=<n>]]
+                 [--baseline <file>] [--save <file>]
+       aoc viz <year> <day> [--part <1|2>] [--sample[=<n>]] [--fps <n>]
+               [--gif <file> | --frames <dir> [--ppm]] [--scale <n>]
+       aoc fetch <year> [<day>]
+       aoc submit <year> <day> <part> [<answer>]
+
+The input defaults to <year>/input/<day>.txt, --sample picks <day>_sample.txt
+and --sample=2 <day>_sample2.txt. Pass a file name or - (stdin) to override.
+Without a day, every day with an input is run. --format json and csv print a
+record per part with the answer and the time taken in nanoseconds.";
+
+const YEARS: &[Year] = &[aoc2021::YEAR, aoc2023::YEAR];
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(e) = run(&args) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    match args.first().map(String::as_str) {
+        Some("run") => cmd_run(&args[1..]),
+        Some("bench") => bench::cmd_bench(&args[1..]),
+        Some("viz") => viz::cmd_viz(&args[1..]),
+        Some("fetch") => online::cmd_fetch(&args[1..]),
+        Some("submit") => online::cmd_submit(&args[1..]),
+        Some(cmd) => Err(format!("unknown command {:?}\n{}", cmd, USAGE)),
+        None => Err(USAGE.to_string()),
+    }
+}
+
+fn cmd_run(args: &[String]) -> Result<(), String> {
+    let mut positional = Vec::new();
+    let mut parts = Part::ALL.to_vec();
+    let mut sample = None;
+    let mut format = Format::Human;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-p" | "--part" => {
+                let part = args.next().ok_or("--part expects a value")?;
+                parts = vec![part.parse()?];
+            }
+            "-f" | "--format" => format = args.next().ok_or("--format expects a value")?.parse()?,
+            arg => match parse_sample(arg)? {
+                Some(n) => sample = Some(n),
+                None => positional.push(arg),
+            },
+        }
+    }
+    let (year, day, file) = match positional[..] {
+        [year] => (year, None, None),
+        [year, day] => (year, Some(day), None),
+        [year, day, file] => (year, Some(day), Some(file)),
+        _ => return Err(USAGE.to_string()),
+    };
+    let year = find_year(year)?;
+    let variant = sample.map_or(Variant::Puzzle, Variant::Sample);
+
+    let Some(day) = day else {
+        // A whole year skips missing inputs and reports broken days as failed
+        // parts, so that one of them doesn't cost the others' results.
+        let mut runs = Vec::new();
+        for day in year.days {
+            let run = match input::read(Path::new(year.input_dir), day.day, variant) {
+                Ok(input) => match panic::catch_unwind(|| day.run(year.year, &input, &parts)) {
+                    Ok(Ok(run)) => run,
+                    Ok(Err(e)) => failed_run(year, day, &parts, format!("malformed input, {}", e)),
+                    Err(_) => failed_run(year, day, &parts, "panicked".to_string()),
+                },
+                Err(InputError::NotFound { .. }) => continue,
+                Err(e) => failed_run(year, day, &parts, e.to_string()),
+            };
+            runs.push(run);
+        }
+        print!("{}", report::render(&runs, format));
+        return check_failed(&runs);
+    };
+    let solution = find_day(year, day)?;
+
+    let input = match (file, sample) {
+        (Some(_), Some(_)) => return Err("--sample can't be combined with an input file".into()),
+        (Some("-"), None) => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("failed to read stdin: {}", e))?;
+            input
+        }
+        (Some(file), None) => {
+            std::fs::read_to_string(file).map_err(|e| format!("failed to read {}: {}", file, e))?
+        }
+        (None, _) => input::read(Path::new(year.input_dir), solution.day, variant)
+            .map_err(|e| e.to_string())?,
+    };
+
+    // parts catch their own panics, this is for the parser's; the panic
+    // message is already out, the report only needs to say so
+    let run = match panic::catch_unwind(|| solution.run(year.year, &input, &parts)) {
+        Ok(run) => run.map_err(|e| parse_error(&e, &input))?,
+        Err(_) => failed_run(year, solution, &parts, "panicked".to_string()),
+    };
+    let runs = [run];
+    print!("{}", report::render(&runs, format));
+    check_failed(&runs)
+}
+
+/// A run of `day` in which every one of `parts` failed for `reason`.
+fn failed_run(year: &Year, day: &Day, parts: &[Part], reason: String) -> Run {
+    Run {
+        year: year.year,
+        day: day.day,
+        parse: Duration::ZERO,
+        parts: parts
+            .iter()
+            .map(|&part| PartRun {
+                part,
+                answer: Value::Failed(reason.clone()),
+                time: Duration::ZERO,
+            })
+            .collect(),
+    }
+}
+
+// Fails the command once the report is out if any part found no answer.
+fn check_failed(runs: &[Run]) -> Result<(), String> {
+    let failed = runs
+        .iter()
+        .flat_map(|run| &run.parts)
+        .filter(|part| part.answer.is_failed())
+        .count();
+    match failed {
+        0 => Ok(()),
+        1 => Err("1 part failed".to_string()),
+        n => Err(format!("{} parts failed", n)),
+    }
+}
+
+/// Recognizes `-s`/`--sample` (the first sample) and `--sample=<n>`.
+fn parse_sample(arg: &str) -> Result<Option<u32>, String> {
+    match arg {
+        "-s" | "--sample" => Ok(Some(1)),
+        _ => match arg.strip_prefix("--sample=") {
+            Some(n) => n
+                .parse()
+                .ok()
+                .filter(|&n| n > 0)
+                .map(Some)
+                .ok_or_else(|| format!("invalid sample number {:?}", n)),
+            None => Ok(None),
+        },
+    }
+}
+
+fn find_year(year: &str) -> Result<&'static Year, String> {
+    let year: u16 = year
+        .parse()
+        .map_err(|_| format!("invalid year {:?}", year))?;
+    YEARS
+        .iter()
+        .find(|y| y.year == year)
+        .ok_or_else(|| format!("no solutions for year {}", year))
+}
+
+fn find_day(year: &'static Year, day: &str) -> Result<&'static Day, String> {
+    let day: u8 = day.parse().map_err(|_| format!("invalid day {:?}", day))?;
+    year.day(day)
+        .ok_or_else(|| format!("no solution for {} day {}", year.year, day))
+}
+
+/// Formats `e` along with the offending line of `input`.
+fn parse_error(e: &ParseError, input: &str) -> String {
+    match e.excerpt(input) {
+        Some(excerpt) => format!("malformed input, {}\n{}", e, excerpt),
+        None => format!("malformed input, {}", e),
+    }
+}
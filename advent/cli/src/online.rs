@@ -0,0 +1,161 @@
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use aoc::guesses::{Guess, History, Outcome};
+use aoc::input;
+use aoc::online::{self, Client, Fetched, Verdict};
+use aoc::report::Value;
+use aoc::{Part, Variant};
+
+use crate::{find_day, find_year, parse_error};
+
+const FETCH_USAGE: &str = "\
+usage: aoc fetch <year> [<day>]
+
+Downloads puzzle inputs into <year>/input, every open day of the year unless
+a day is given. Inputs already there are left alone. The session cookie is
+read from AOC_SESSION or ~/.config/aoc/session, and requests are spaced out
+by at least five seconds, across runs too.";
+
+const SUBMIT_USAGE: &str = "\
+usage: aoc submit <year> <day> <part> [<answer>]
+
+Submits the answer the day's solution gives for its puzzle input, or the
+answer given. Every guess is kept in ~/.cache/aoc/guesses.txt and one that
+can't be right is never sent: a repeat of a wrong answer, or one at or past
+an answer that was too high or too low. Nothing is sent either while the
+site still makes you wait after a wrong answer.";
+
+pub fn cmd_fetch(args: &[String]) -> Result<(), String> {
+    let (year, days) = match args {
+        [year] => {
+            let last = if year.parse::<u16>().is_ok_and(|y| y >= 2025) {
+                12
+            } else {
+                25
+            };
+            (year, (1..=last).collect::<Vec<u8>>())
+        }
+        [year, day] => (year, vec![parse_day(day)?]),
+        _ => return Err(FETCH_USAGE.to_string()),
+    };
+    let year = find_year(year)?;
+    let mut client = Client::from_env().map_err(|e| e.to_string())?;
+    let whole_year = days.len() > 1;
+    for day in days {
+        // a whole year stops quietly at the first day that isn't open
+        if whole_year && online::unlock_time(year.year, day) > SystemTime::now() {
+            break;
+        }
+        let fetched = online::fetch_input(&mut client, Path::new(year.input_dir), year.year, day)
+            .map_err(|e| format!("{} day {}: {}", year.year, day, e))?;
+        match fetched {
+            Fetched::Cached(path) => {
+                println!("{} day {}: cached {}", year.year, day, path.display())
+            }
+            Fetched::Downloaded(path) => {
+                println!("{} day {}: downloaded {}", year.year, day, path.display())
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn cmd_submit(args: &[String]) -> Result<(), String> {
+    let (year, day, part, answer) = match args {
+        [year, day, part] => (year, day, part, None),
+        [year, day, part, answer] => (year, day, part, Some(answer.trim().to_string())),
+        _ => return Err(SUBMIT_USAGE.to_string()),
+    };
+    let year = find_year(year)?;
+    let part: Part = part.parse()?;
+    let (day, answer) = match answer {
+        // a day solved in another language can still be submitted by hand
+        Some(answer) => (parse_day(day)?, answer),
+        None => {
+            let solution = find_day(year, day)?;
+            let input = input::read(Path::new(year.input_dir), solution.day, Variant::Puzzle)
+                .map_err(|e| e.to_string())?;
+            let run = solution
+                .run(year.year, &input, &[part])
+                .map_err(|e| parse_error(&e, &input))?;
+            match &run.parts[0].answer {
+                Value::Failed(e) => return Err(format!("the solution found no answer: {}", e)),
+                answer => (solution.day, answer.to_string()),
+            }
+        }
+    };
+    if answer.is_empty() {
+        return Err("the answer is empty".to_string());
+    }
+    let prefix = format!("{} day {} part {}", year.year, day, part);
+
+    let mut history = History::load(&online::history_file())?;
+    history
+        .check(year.year, day, part, &answer)
+        .map_err(|e| format!("{}: not submitting {}, {}", prefix, answer, e))?;
+    let mut client = Client::from_env().map_err(|e| e.to_string())?;
+    let verdict = client
+        .submit(year.year, day, part, &answer)
+        .map_err(|e| format!("{}: {}", prefix, e))?;
+
+    let (outcome, wait) = match verdict {
+        Verdict::Judged(outcome, wait) => (outcome, wait),
+        Verdict::Wait(left) => {
+            return Err(format!(
+                "{}: not submitting {}, wait {} after the last wrong answer",
+                prefix,
+                answer,
+                duration(left)
+            ))
+        }
+        Verdict::WrongLevel => {
+            return Err(format!(
+                "{}: the site won't take an answer, the part is solved already or part 1 isn't",
+                prefix
+            ))
+        }
+    };
+    history
+        .record(Guess {
+            year: year.year,
+            day,
+            part,
+            outcome,
+            answer: answer.clone(),
+        })
+        .map_err(|e| format!("failed to record the guess: {}", e))?;
+    let what = match outcome {
+        Outcome::Right => {
+            println!("{}: {} is right", prefix, answer);
+            return Ok(());
+        }
+        Outcome::TooHigh => "too high",
+        Outcome::TooLow => "too low",
+        Outcome::Wrong => "wrong",
+    };
+    Err(format!(
+        "{}: {} is {}, the next answer can go in {}",
+        prefix,
+        answer,
+        what,
+        duration(wait)
+    ))
+}
+
+// Any day of the calendar, whether there's a solution for it or not.
+fn parse_day(day: &str) -> Result<u8, String> {
+    day.parse()
+        .ok()
+        .filter(|d| (1..=25).contains(d))
+        .ok_or_else(|| format!("invalid day {:?}", day))
+}
+
+// Like "1m05s", or "40s" under a minute.
+fn duration(d: Duration) -> String {
+    let secs = d.as_secs_f64().ceil() as u64;
+    match secs {
+        0..60 => format!("{}s", secs),
+        _ => format!("{}m{:02}s", secs / 60, secs % 60),
+    }
+}
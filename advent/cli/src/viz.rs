@@ -0,0 +1,373 @@
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc::image::{Animation, FrameDir, Gif, ImageFormat, Palette, Rgb};
+use aoc::input;
+use aoc::viz::{Scene, Screen};
+use aoc::{Part, Variant};
+
+use crate::{find_day, find_year, parse_error, parse_sample};
+
+const USAGE: &str = "\
+usage: aoc viz <year> <day> [--part <1|2>] [--sample[=<n>]] [--fps <n>]
+               [--gif <file> | --frames <dir> [--ppm]] [--scale <n>]
+
+Plays the simulation behind a day in the terminal. Space pauses, n steps
+while paused, + and - change the speed and q quits. --gif writes every step
+to an animated GIF instead, and --frames to a numbered PNG per step in a
+directory, or PPM with --ppm. Each cell is a square of --scale pixels.";
+
+// The colour paths are drawn in, on top of the day's own.
+const HIGHLIGHT: Rgb = [240, 60, 200];
+
+pub fn cmd_viz(args: &[String]) -> Result<(), String> {
+    let mut positional = Vec::new();
+    let mut part = Part::One;
+    let mut sample = None;
+    let mut fps = 10;
+    let mut file = None;
+    let mut ppm = false;
+    let mut scale = 8;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut number = |flag: &str| -> Result<u32, String> {
+            let n = args.next().ok_or(format!("{} expects a value", flag))?;
+            n.parse()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or_else(|| format!("invalid {} {:?}", &flag[2..], n))
+        };
+        match arg.as_str() {
+            "-p" | "--part" => part = args.next().ok_or("--part expects a value")?.parse()?,
+            "--fps" => fps = number("--fps")?,
+            "--scale" => scale = number("--scale")? as usize,
+            "--gif" | "--frames" if file.is_some() => {
+                return Err("--gif and --frames can't be combined".to_string())
+            }
+            "--gif" => {
+                let path = args.next().ok_or("--gif expects a file")?;
+                file = Some(Target::Gif(PathBuf::from(path)));
+            }
+            "--frames" => {
+                let dir = args.next().ok_or("--frames expects a directory")?;
+                file = Some(Target::Frames(PathBuf::from(dir)));
+            }
+            "--ppm" => ppm = true,
+            arg => match parse_sample(arg)? {
+                Some(n) => sample = Some(n),
+                None => positional.push(arg),
+            },
+        }
+    }
+    let [year, day] = positional[..] else {
+        return Err(USAGE.to_string());
+    };
+    let year = find_year(year)?;
+    let day = find_day(year, day)?;
+    let palette = day
+        .palette()
+        .ok_or_else(|| format!("{} day {} has no visualisation", year.year, day.day))?;
+    let variant = sample.map_or(Variant::Puzzle, Variant::Sample);
+    let input =
+        input::read(Path::new(year.input_dir), day.day, variant).map_err(|e| e.to_string())?;
+
+    if ppm && !matches!(file, Some(Target::Frames(_))) {
+        return Err("--ppm only goes with --frames".to_string());
+    }
+
+    match file {
+        Some(target) => {
+            let mut screen = FileScreen {
+                target,
+                format: if ppm {
+                    ImageFormat::Ppm
+                } else {
+                    ImageFormat::Png
+                },
+                palette,
+                scale,
+                delay: (100 / fps).max(1) as u16,
+                output: None,
+                error: None,
+            };
+            day.visualize(&input, part, &mut screen)
+                .map_err(|e| parse_error(&e, &input))?;
+            screen.finish()
+        }
+        None => {
+            let mut screen = Terminal::open(palette, fps)?;
+            day.visualize(&input, part, &mut screen)
+                .map_err(|e| parse_error(&e, &input))?;
+            screen.wait_for_exit();
+            Ok(())
+        }
+    }
+}
+
+/// Where `aoc viz` writes its steps instead of showing them.
+enum Target {
+    Gif(PathBuf),
+    Frames(PathBuf),
+}
+
+// A target once the first scene has opened it.
+enum Output {
+    Gif(Gif<BufWriter<File>>),
+    Frames(FrameDir),
+}
+
+/// Writes every scene to a GIF, sized after the first one, or to a
+/// directory of pictures.
+struct FileScreen {
+    target: Target,
+    format: ImageFormat,
+    palette: Palette,
+    scale: usize,
+    delay: u16,
+    output: Option<Output>,
+    error: Option<String>,
+}
+
+impl FileScreen {
+    fn push(&mut self, scene: &Scene) -> io::Result<()> {
+        // paths get a colour of their own, after the day's
+        let highlight = u8::try_from(self.palette.colors().len()).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the day's 256 colours leave no room for the path's",
+            )
+        })?;
+        let frame = scene.frame(highlight).scaled(self.scale);
+        let output = match &mut self.output {
+            Some(output) => output,
+            None => {
+                let mut colors = self.palette.colors().to_vec();
+                colors.push(HIGHLIGHT);
+                let palette = Palette::new(colors);
+                let output = match &self.target {
+                    Target::Gif(path) => {
+                        let out = BufWriter::new(File::create(path)?);
+                        Output::Gif(Gif::new(
+                            out,
+                            &palette,
+                            frame.width(),
+                            frame.height(),
+                            self.delay,
+                        )?)
+                    }
+                    Target::Frames(dir) => {
+                        Output::Frames(FrameDir::create(dir, &palette, self.format)?)
+                    }
+                };
+                self.output.insert(output)
+            }
+        };
+        match output {
+            Output::Gif(gif) => gif.push(&frame),
+            Output::Frames(dir) => dir.push(&frame),
+        }
+    }
+
+    fn finish(self) -> Result<(), String> {
+        let path = match &self.target {
+            Target::Gif(path) | Target::Frames(path) => path,
+        };
+        let failed =
+            |e: &dyn std::fmt::Display| format!("failed to write {}: {}", path.display(), e);
+        if let Some(e) = &self.error {
+            return Err(failed(e));
+        }
+        match self.output.ok_or("the simulation has no steps")? {
+            Output::Gif(gif) => gif
+                .finish()
+                .and_then(|mut out| out.flush())
+                .map_err(|e| failed(&e)),
+            Output::Frames(dir) => {
+                println!("{} frames in {}", dir.count(), path.display());
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Screen for FileScreen {
+    fn show(&mut self, scene: &Scene) -> bool {
+        match self.push(scene) {
+            Ok(()) => true,
+            Err(e) => {
+                self.error = Some(e.to_string());
+                false
+            }
+        }
+    }
+}
+
+/// Draws scenes in place on the terminal and paces them, taking keys in raw
+/// mode from a thread of its own. The terminal is put back when dropped.
+struct Terminal {
+    palette: Palette,
+    fps: u32,
+    paused: bool,
+    quit: bool,
+    keys: Receiver<u8>,
+    // rows and columns to draw in, leaving two lines for the status
+    size: (usize, usize),
+    saved_mode: String,
+}
+
+impl Terminal {
+    fn open(palette: Palette, fps: u32) -> Result<Terminal, String> {
+        let saved_mode =
+            stty(&["-g"]).map_err(|e| format!("aoc viz needs a terminal, or --gif: {}", e))?;
+        let size = stty(&["size"])?;
+        let size: (usize, usize) = match size.split_whitespace().collect::<Vec<_>>()[..] {
+            // a terminal that doesn't know its size says 0 0
+            [rows, cols] => (
+                rows.parse().ok().filter(|&n| n > 0).unwrap_or(24),
+                cols.parse().ok().filter(|&n| n > 0).unwrap_or(80),
+            ),
+            _ => (24, 80),
+        };
+        let mut tty =
+            File::open("/dev/tty").map_err(|e| format!("failed to open /dev/tty: {}", e))?;
+        stty(&["raw", "-echo"])?;
+
+        let (send, keys) = mpsc::channel();
+        thread::spawn(move || {
+            let mut key = [0];
+            while tty.read_exact(&mut key).is_ok() && send.send(key[0]).is_ok() {}
+        });
+        print!("\x1b[?25l\x1b[2J");
+        Ok(Terminal {
+            palette,
+            fps,
+            paused: false,
+            quit: false,
+            keys,
+            size: (size.0.saturating_sub(2), size.1),
+            saved_mode,
+        })
+    }
+
+    fn draw(&self, scene: &Scene) {
+        let path = scene.path.iter().collect::<HashSet<_>>();
+        let (rows, cols) = (
+            scene.cells.rows().min(self.size.0),
+            scene.cells.cols().min(self.size.1),
+        );
+        let mut out = String::from("\x1b[H");
+        for r in 0..rows {
+            let mut style = None;
+            for (c, &(ch, color)) in scene.cells.row(r)[..cols].iter().enumerate() {
+                let on_path = path.contains(&(r, c));
+                if style != Some((color, on_path)) {
+                    let [red, green, blue] = self.palette.colors()[color as usize];
+                    write!(out, "\x1b[0;38;2;{};{};{}m", red, green, blue).unwrap();
+                    if on_path {
+                        let [red, green, blue] = HIGHLIGHT;
+                        write!(out, "\x1b[48;2;{};{};{}m", red, green, blue).unwrap();
+                    }
+                    style = Some((color, on_path));
+                }
+                out.push(ch);
+            }
+            out.push_str("\x1b[0m\x1b[K\r\n");
+        }
+        write!(out, "\x1b[J{}\r\n", scene.caption).unwrap();
+        print!("{}", out);
+        self.status(None);
+    }
+
+    fn status(&self, message: Option<&str>) {
+        let state = if self.paused { "paused" } else { "playing" };
+        let help = message.unwrap_or("space pause, n step, +/- speed, q quit");
+        print!(
+            "\x1b[7m {} at {} fps \x1b[0m {}\x1b[K\r",
+            state, self.fps, help
+        );
+        io::stdout().flush().unwrap();
+    }
+
+    // Leaves the last scene up once the simulation ends by itself.
+    fn wait_for_exit(&mut self) {
+        if self.quit {
+            return;
+        }
+        self.status(Some("finished, press any key"));
+        let _ = self.keys.recv();
+    }
+}
+
+impl Screen for Terminal {
+    fn show(&mut self, scene: &Scene) -> bool {
+        self.draw(scene);
+        let mut due = Instant::now() + Duration::from_secs(1) / self.fps;
+        loop {
+            let key = if self.paused {
+                self.keys.recv().map_err(|_| RecvTimeoutError::Disconnected)
+            } else {
+                self.keys
+                    .recv_timeout(due.saturating_duration_since(Instant::now()))
+            };
+            match key {
+                Ok(b'q' | 3) => {
+                    self.quit = true;
+                    return false;
+                }
+                Ok(b' ') => {
+                    self.paused = !self.paused;
+                    due = Instant::now() + Duration::from_secs(1) / self.fps;
+                }
+                Ok(b'n' | b'.') if self.paused => break,
+                Ok(b'+' | b'=') => self.fps = (self.fps * 2).min(240),
+                Ok(b'-' | b'_') => self.fps = (self.fps / 2).max(1),
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout) => break,
+                // without a keyboard there's no getting out of a pause
+                Err(RecvTimeoutError::Disconnected) if self.paused => {
+                    self.quit = true;
+                    return false;
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    thread::sleep(due.saturating_duration_since(Instant::now()));
+                    break;
+                }
+            }
+            self.status(None);
+        }
+        true
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = stty(&[self.saved_mode.trim()]);
+        print!("\x1b[0m\x1b[?25h\r\n");
+        let _ = io::stdout().flush();
+    }
+}
+
+// Runs stty on the controlling terminal, returning what it prints.
+fn stty(args: &[&str]) -> Result<String, String> {
+    let tty = File::open("/dev/tty").map_err(|e| format!("failed to open /dev/tty: {}", e))?;
+    let out = Command::new("stty")
+        .args(args)
+        .stdin(tty)
+        .output()
+        .map_err(|e| format!("failed to run stty: {}", e))?;
+    if !out.status.success() {
+        return Err(format!(
+            "stty {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&out.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+}